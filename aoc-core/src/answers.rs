@@ -0,0 +1,202 @@
+use serde::Deserialize;
+use snafu::prelude::*;
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// What is known about the answers for one day's real input, as
+/// recorded in that day's `answers.toml`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Answers {
+    part1: Option<Part>,
+    part2: Option<Part>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Part {
+    answer: Option<String>,
+
+    #[serde(default)]
+    rejected: Vec<Attempt>,
+}
+
+/// A submitted answer that was not accepted.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Attempt {
+    pub answer: String,
+    pub verdict: Verdict,
+
+    /// What was wrong with the solution that produced this answer.
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Wrong => "wrong",
+        }
+        .fmt(f)
+    }
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let s = fs::read_to_string(path).context(ReadSnafu { path })?;
+        toml::from_str(&s).context(ParseSnafu { path })
+    }
+
+    /// The accepted answer for the given part, if one is known.
+    pub fn accepted(&self, part: u8) -> Option<&str> {
+        self.part(part)?.answer.as_deref()
+    }
+
+    /// The answers submitted for the given part that were not accepted.
+    pub fn rejected(&self, part: u8) -> &[Attempt] {
+        self.part(part).map_or(&[], |p| &p.rejected)
+    }
+
+    /// Checks a candidate answer against the rejected attempts,
+    /// returning why it cannot be right.
+    pub fn check(&self, part: u8, answer: &str) -> Option<Warning<'_>> {
+        let rejected = self.rejected(part);
+
+        if let Some(attempt) = rejected.iter().find(|a| a.answer == answer) {
+            return Some(Warning::Rejected { attempt });
+        }
+
+        let answer = answer.parse::<i128>().ok()?;
+        let bound = |verdict| {
+            rejected
+                .iter()
+                .filter(move |a| a.verdict == verdict)
+                .filter_map(|a| Some((a.answer.parse::<i128>().ok()?, a)))
+        };
+
+        let too_low = bound(Verdict::TooLow)
+            .filter(|&(v, _)| answer < v)
+            .max_by_key(|&(v, _)| v);
+        if let Some((_, attempt)) = too_low {
+            return Some(Warning::TooLow { attempt });
+        }
+
+        let too_high = bound(Verdict::TooHigh)
+            .filter(|&(v, _)| answer > v)
+            .min_by_key(|&(v, _)| v);
+        if let Some((_, attempt)) = too_high {
+            return Some(Warning::TooHigh { attempt });
+        }
+
+        None
+    }
+
+    fn part(&self, part: u8) -> Option<&Part> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Why a candidate answer is known to be wrong.
+#[derive(Debug, Copy, Clone)]
+pub enum Warning<'a> {
+    /// The same answer was already rejected.
+    Rejected { attempt: &'a Attempt },
+
+    /// The answer is below one that was already too low.
+    TooLow { attempt: &'a Attempt },
+
+    /// The answer is above one that was already too high.
+    TooHigh { attempt: &'a Attempt },
+}
+
+impl fmt::Display for Warning<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::Rejected { attempt } => {
+                write!(f, "was already rejected as {}", attempt.verdict)
+            }
+            Warning::TooLow { attempt } => {
+                write!(f, "is less than {}, which was too low", attempt.answer)
+            }
+            Warning::TooHigh { attempt } => {
+                write!(f, "is greater than {}, which was too high", attempt.answer)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Could not read the answers from {}", path.display()))]
+    Read { source: io::Error, path: PathBuf },
+
+    #[snafu(display("Could not parse the answers in {}", path.display()))]
+    Parse {
+        source: toml::de::Error,
+        path: PathBuf,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ANSWERS: &str = r#"
+        [part2]
+        answer = "36919"
+        rejected = [
+            { answer = "37005", verdict = "too-high" },
+            { answer = "28945", verdict = "too-low" },
+            { answer = "30000", verdict = "wrong" },
+        ]
+    "#;
+
+    #[test]
+    #[snafu::report]
+    fn answers_within_bounds_pass() -> Result<(), toml::de::Error> {
+        let answers: Answers = toml::from_str(ANSWERS)?;
+
+        assert!(answers.check(2, "36919").is_none());
+        assert!(answers.check(2, "28946").is_none());
+        assert!(answers.check(2, "37004").is_none());
+        assert!(answers.check(1, "1").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn answers_outside_bounds_warn() -> Result<(), toml::de::Error> {
+        let answers: Answers = toml::from_str(ANSWERS)?;
+
+        let w = answers.check(2, "37005");
+        assert!(matches!(w, Some(Warning::Rejected { .. })), "{w:?}");
+
+        let w = answers.check(2, "30000");
+        assert!(matches!(w, Some(Warning::Rejected { .. })), "{w:?}");
+
+        let w = answers.check(2, "100");
+        assert!(matches!(w, Some(Warning::TooLow { .. })), "{w:?}");
+
+        let w = answers.check(2, "40000");
+        assert!(matches!(w, Some(Warning::TooHigh { .. })), "{w:?}");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use snafu::prelude::*;
+use std::{
+    borrow::Cow,
+    env,
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+/// The path that refers to stdin instead of a file.
+pub const STDIN_PATH: &str = "-";
+
+/// Reads the puzzle input named by the program's only argument.
+///
+/// When no argument is given, the `embedded` input is used if there
+/// is one; otherwise the input is read from stdin.
+pub fn from_args(embedded: Option<&'static str>) -> Result<Cow<'static, str>, Error> {
+    match (path_from_args()?, embedded) {
+        (Some(path), _) => read(path.as_ref()).map(Cow::Owned),
+        (None, Some(embedded)) => Ok(Cow::Borrowed(embedded)),
+        (None, None) => read_stdin().map(Cow::Owned),
+    }
+}
+
+/// Like [`from_args`], but hands back a reader over the input instead
+/// of reading it all up front, for puzzles solved a line at a time.
+pub fn reader_from_args(embedded: Option<&'static str>) -> Result<Box<dyn BufRead>, Error> {
+    match (path_from_args()?, embedded) {
+        (Some(path), _) => open(path.as_ref()),
+        (None, Some(embedded)) => Ok(Box::new(embedded.as_bytes())),
+        (None, None) => Ok(Box::new(io::stdin().lock())),
+    }
+}
+
+fn path_from_args() -> Result<Option<OsString>, Error> {
+    let mut args = env::args_os().skip(1);
+    let path = args.next();
+    ensure!(args.next().is_none(), TooManyArgumentsSnafu);
+    Ok(path)
+}
+
+/// Reads the puzzle input from a file, or from stdin when the path
+/// is [`STDIN_PATH`].
+pub fn read(path: &Path) -> Result<String, Error> {
+    if path == Path::new(STDIN_PATH) {
+        read_stdin()
+    } else {
+        fs::read_to_string(path).context(ReadFileSnafu { path })
+    }
+}
+
+/// Opens the puzzle input in a file, or stdin when the path is
+/// [`STDIN_PATH`], without reading it yet.
+pub fn open(path: &Path) -> Result<Box<dyn BufRead>, Error> {
+    if path == Path::new(STDIN_PATH) {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        let file = File::open(path).context(ReadFileSnafu { path })?;
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+fn read_stdin() -> Result<String, Error> {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .context(ReadStdinSnafu)?;
+    Ok(input)
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Expected at most one argument, the path to the puzzle input"))]
+    TooManyArguments,
+
+    #[snafu(display("Could not read the puzzle input from {}", path.display()))]
+    ReadFile { source: io::Error, path: PathBuf },
+
+    #[snafu(display("Could not read the puzzle input from stdin"))]
+    ReadStdin { source: io::Error },
+}
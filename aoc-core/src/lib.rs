@@ -0,0 +1,7 @@
+//! Pieces shared by every day's binary and the `aoc` runner.
+
+pub mod answers;
+pub mod input;
+mod solution;
+
+pub use solution::{Solution, Unsolved};
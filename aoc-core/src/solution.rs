@@ -0,0 +1,40 @@
+use std::{any::TypeId, error::Error, fmt};
+
+/// One day's puzzle: how to parse the input and how to answer each
+/// part from the parsed input.
+pub trait Solution {
+    /// The parsed puzzle input, which may borrow from the raw input.
+    type Input<'a>;
+
+    /// Anything that can go wrong while parsing or solving.
+    type Error: Error + Send + Sync + 'static;
+
+    /// The answer to part 1, or [`Unsolved`].
+    type Part1: fmt::Display + 'static;
+
+    /// The answer to part 2, or [`Unsolved`].
+    type Part2: fmt::Display + 'static;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error>;
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error>;
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error>;
+}
+
+/// The answer to a part that has not been solved yet.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Unsolved;
+
+impl Unsolved {
+    /// If the answer type `T` marks a part as unsolved.
+    pub fn is<T: 'static>() -> bool {
+        TypeId::of::<T>() == TypeId::of::<Self>()
+    }
+}
+
+impl fmt::Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "not solved".fmt(f)
+    }
+}
@@ -0,0 +1,190 @@
+//! Times parsing and each part of every day against the examples from
+//! the puzzle descriptions, so the suite runs without anyone's
+//! personal input.
+//!
+//! Run a single day with `cargo bench -p aoc --bench examples -- day-05`.
+
+use aoc_core::{Solution, Unsolved};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+macro_rules! example {
+    ($day:literal, $name:literal) => {
+        include_str!(concat!("../../", $day, "/", $name))
+    };
+}
+
+/// The example each part is timed against. Some puzzles use a
+/// different example for each part and some have none for part 2.
+struct Examples {
+    part1: &'static str,
+    part2: Option<&'static str>,
+}
+
+impl Examples {
+    const fn both(input: &'static str) -> Self {
+        Self::split(input, input)
+    }
+
+    const fn split(part1: &'static str, part2: &'static str) -> Self {
+        Self {
+            part1,
+            part2: Some(part2),
+        }
+    }
+
+    const fn part1_only(part1: &'static str) -> Self {
+        Self { part1, part2: None }
+    }
+}
+
+fn bench_day<S: Solution>(c: &mut Criterion, name: &str, examples: Examples) {
+    let mut group = c.benchmark_group(name);
+
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(examples.part1))));
+
+    if !Unsolved::is::<S::Part1>() {
+        let input = S::parse(examples.part1).expect("The part 1 example is valid");
+        group.bench_function("part 1", |b| b.iter(|| S::part1(black_box(&input))));
+    }
+
+    if let Some(part2) = examples.part2 {
+        if !Unsolved::is::<S::Part2>() {
+            let input = S::parse(part2).expect("The part 2 example is valid");
+            group.bench_function("part 2", |b| b.iter(|| S::part2(black_box(&input))));
+        }
+    }
+
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day_01::Day01>(
+        c,
+        "day-01",
+        Examples::split(
+            example!("day-01", "example-input-1"),
+            example!("day-01", "example-input-2"),
+        ),
+    );
+    bench_day::<day_02::Day02>(
+        c,
+        "day-02",
+        Examples::both(example!("day-02", "example-input-1")),
+    );
+    bench_day::<day_03::Day03>(
+        c,
+        "day-03",
+        Examples::both(example!("day-03", "example-input-1")),
+    );
+    bench_day::<day_04::Day04>(
+        c,
+        "day-04",
+        Examples::both(example!("day-04", "example-input-1")),
+    );
+    bench_day::<day_05::Day05>(
+        c,
+        "day-05",
+        Examples::both(example!("day-05", "example-input-1")),
+    );
+    bench_day::<day_06::Day06>(
+        c,
+        "day-06",
+        Examples::both(example!("day-06", "example-input-1")),
+    );
+    bench_day::<day_07::Day07>(
+        c,
+        "day-07",
+        Examples::both(example!("day-07", "example-input-1")),
+    );
+    bench_day::<day_08::Day08>(
+        c,
+        "day-08",
+        Examples::split(
+            example!("day-08", "example-input-1"),
+            example!("day-08", "example-input-3"),
+        ),
+    );
+    bench_day::<day_09::Day09>(
+        c,
+        "day-09",
+        Examples::both(example!("day-09", "example-input-1")),
+    );
+    bench_day::<day_10::Day10>(
+        c,
+        "day-10",
+        Examples::split(
+            example!("day-10", "example-input-2"),
+            example!("day-10", "example-input-5"),
+        ),
+    );
+    bench_day::<day_11::Day11>(
+        c,
+        "day-11",
+        Examples::both(example!("day-11", "example-input-1")),
+    );
+    bench_day::<day_12::Day12>(
+        c,
+        "day-12",
+        Examples::both(example!("day-12", "example-input-1")),
+    );
+    bench_day::<day_13::Day13>(
+        c,
+        "day-13",
+        Examples::both(example!("day-13", "example-input-1")),
+    );
+    bench_day::<day_14::Day14>(
+        c,
+        "day-14",
+        Examples::both(example!("day-14", "example-input-1")),
+    );
+    bench_day::<day_15::Day15>(
+        c,
+        "day-15",
+        Examples::both(example!("day-15", "example-input-1")),
+    );
+    bench_day::<day_16::Day16>(
+        c,
+        "day-16",
+        Examples::both(example!("day-16", "example-input-1")),
+    );
+    bench_day::<day_17::Day17>(
+        c,
+        "day-17",
+        Examples::both(example!("day-17", "example-input-1")),
+    );
+    bench_day::<day_18::Day18>(
+        c,
+        "day-18",
+        Examples::both(example!("day-18", "example-input-1")),
+    );
+    bench_day::<day_19::Day19>(
+        c,
+        "day-19",
+        Examples::both(example!("day-19", "example-input-1")),
+    );
+    // Part 2 needs an `rx` module, which none of the examples have
+    bench_day::<day_20::Day20>(
+        c,
+        "day-20",
+        Examples::part1_only(example!("day-20", "example-input-2")),
+    );
+    bench_day::<day_21::Day21>(
+        c,
+        "day-21",
+        Examples::both(example!("day-21", "example-input-1")),
+    );
+    bench_day::<day_22::Day22>(
+        c,
+        "day-22",
+        Examples::both(example!("day-22", "example-input-1")),
+    );
+    bench_day::<day_23::Day23>(
+        c,
+        "day-23",
+        Examples::both(example!("day-23", "example-input-1")),
+    );
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
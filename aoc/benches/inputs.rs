@@ -0,0 +1,40 @@
+//! Times parsing and both parts of the days whose solutions were tuned
+//! for speed against the real puzzle input, where the examples are too
+//! small to show the difference. The inputs are read at run time, and
+//! any day without one is skipped.
+//!
+//! Run a single day with `cargo bench -p aoc --bench inputs -- day-17`.
+
+use aoc_core::Solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::{fs, hint::black_box};
+
+fn bench_day<S: Solution>(c: &mut Criterion, day: u8) {
+    let name = format!("day-{day:02}");
+    let path = aoc::input_path(day);
+
+    let Ok(input) = fs::read_to_string(&path) else {
+        eprintln!("Skipping {name}: no input at {}", path.display());
+        return;
+    };
+
+    let mut group = c.benchmark_group(name);
+
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&input))));
+
+    let parsed = S::parse(&input).expect("The input is valid");
+
+    group.bench_function("part 1", |b| b.iter(|| S::part1(black_box(&parsed))));
+    group.bench_function("part 2", |b| b.iter(|| S::part2(black_box(&parsed))));
+
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day_14::Day14>(c, 14);
+    bench_day::<day_16::Day16>(c, 16);
+    bench_day::<day_17::Day17>(c, 17);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
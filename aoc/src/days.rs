@@ -0,0 +1,52 @@
+use aoc_core::{Solution, Unsolved};
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Solves one part of a puzzle, returning the rendered answer.
+pub type Solver = fn(&str) -> Result<String, BoxError>;
+
+/// The solvers for each part of the given day, if the day exists.
+pub fn solvers(day: u8) -> Option<[Option<Solver>; 2]> {
+    Some(match day {
+        1 => solvers_for::<day_01::Day01>(),
+        2 => solvers_for::<day_02::Day02>(),
+        3 => solvers_for::<day_03::Day03>(),
+        4 => solvers_for::<day_04::Day04>(),
+        5 => solvers_for::<day_05::Day05>(),
+        6 => solvers_for::<day_06::Day06>(),
+        7 => solvers_for::<day_07::Day07>(),
+        8 => solvers_for::<day_08::Day08>(),
+        9 => solvers_for::<day_09::Day09>(),
+        10 => solvers_for::<day_10::Day10>(),
+        11 => solvers_for::<day_11::Day11>(),
+        12 => solvers_for::<day_12::Day12>(),
+        13 => solvers_for::<day_13::Day13>(),
+        14 => solvers_for::<day_14::Day14>(),
+        15 => solvers_for::<day_15::Day15>(),
+        16 => solvers_for::<day_16::Day16>(),
+        17 => solvers_for::<day_17::Day17>(),
+        18 => solvers_for::<day_18::Day18>(),
+        19 => solvers_for::<day_19::Day19>(),
+        20 => solvers_for::<day_20::Day20>(),
+        21 => solvers_for::<day_21::Day21>(),
+        22 => solvers_for::<day_22::Day22>(),
+        23 => solvers_for::<day_23::Day23>(),
+        _ => return None,
+    })
+}
+
+fn solvers_for<S: Solution>() -> [Option<Solver>; 2] {
+    let part1 = (!Unsolved::is::<S::Part1>()).then_some(part1::<S> as Solver);
+    let part2 = (!Unsolved::is::<S::Part2>()).then_some(part2::<S> as Solver);
+    [part1, part2]
+}
+
+fn part1<S: Solution>(s: &str) -> Result<String, BoxError> {
+    let input = S::parse(s)?;
+    Ok(S::part1(&input)?.to_string())
+}
+
+fn part2<S: Solution>(s: &str) -> Result<String, BoxError> {
+    let input = S::parse(s)?;
+    Ok(S::part2(&input)?.to_string())
+}
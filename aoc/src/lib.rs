@@ -0,0 +1,23 @@
+//! Finds and solves every day's puzzle, for the `aoc` binary and the
+//! answer regression tests.
+
+use std::path::PathBuf;
+
+pub mod days;
+
+/// The crate directory of the given day.
+pub fn day_dir(day: u8) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "..", &format!("day-{day:02}")]
+        .iter()
+        .collect()
+}
+
+/// The day's real puzzle input, which is not present in every checkout.
+pub fn input_path(day: u8) -> PathBuf {
+    day_dir(day).join("input")
+}
+
+/// The day's manifest of accepted answers.
+pub fn answers_path(day: u8) -> PathBuf {
+    day_dir(day).join("answers.toml")
+}
@@ -0,0 +1,109 @@
+use aoc::days;
+use aoc_core::answers::{self, Answers};
+use clap::{Parser, Subcommand};
+use snafu::prelude::*;
+use std::{path::PathBuf, time::Instant};
+
+#[derive(Debug, Parser)]
+#[command(about = "Solve Advent of Code 2023 puzzles")]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solve one day's puzzle
+    Run {
+        /// The day to solve
+        day: u8,
+
+        /// Only solve this part of the puzzle
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// The puzzle input, or `-` for stdin [default: the day's `input` file]
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+}
+
+#[snafu::report]
+fn main() -> Result<(), Error> {
+    let args = Args::parse();
+
+    match args.command {
+        Command::Run { day, part, input } => run(day, part, input),
+    }
+}
+
+fn run(day: u8, part: Option<u8>, input: Option<PathBuf>) -> Result<(), Error> {
+    let solvers = days::solvers(day).context(UnknownDaySnafu { day })?;
+
+    // Known answers only apply to the day's own input
+    let answers = match input {
+        None => known_answers(day)?,
+        Some(_) => Answers::default(),
+    };
+
+    let path = input.unwrap_or_else(|| aoc::input_path(day));
+    let input = aoc_core::input::read(&path).context(InputSnafu)?;
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    for &part in &parts {
+        let Some(solver) = solvers[usize::from(part - 1)] else {
+            // Only an error when the part was explicitly requested
+            ensure!(parts.len() > 1, UnsolvedPartSnafu { day, part });
+            eprintln!("Day {day}, part {part}: not solved");
+            continue;
+        };
+
+        let start = Instant::now();
+        let answer = solver(&input).context(SolveSnafu { day, part })?;
+        let elapsed = start.elapsed();
+
+        if let Some(warning) = answers.check(part, &answer) {
+            eprintln!("Warning: day {day}, part {part}: {answer} {warning}");
+        }
+
+        println!("Day {day}, part {part}: {answer} ({elapsed:?})");
+    }
+
+    Ok(())
+}
+
+fn known_answers(day: u8) -> Result<Answers, Error> {
+    let path = aoc::answers_path(day);
+
+    if path.exists() {
+        Answers::load(&path).context(AnswersSnafu)
+    } else {
+        Ok(Answers::default())
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Day {day} does not exist"))]
+    UnknownDay { day: u8 },
+
+    #[snafu(display("Day {day}, part {part} has not been solved"))]
+    UnsolvedPart { day: u8, part: u8 },
+
+    #[snafu(display("Could not read the puzzle input"))]
+    Input { source: aoc_core::input::Error },
+
+    #[snafu(display("Could not read the known answers"))]
+    Answers { source: answers::Error },
+
+    #[snafu(display("Day {day}, part {part} could not be solved"))]
+    Solve {
+        source: days::BoxError,
+        day: u8,
+        part: u8,
+    },
+}
@@ -0,0 +1,101 @@
+//! Solves each day's real input and compares against the answers
+//! recorded in its `answers.toml`. Days without an `input` file are
+//! skipped.
+//!
+//! The slowest days are ignored by default; run them with
+//! `cargo test --release -p aoc --test answers -- --include-ignored`.
+
+use aoc::days;
+use aoc_core::answers::{self, Answers};
+use snafu::prelude::*;
+
+fn check(day: u8) -> Result<(), Error> {
+    let path = aoc::input_path(day);
+    if !path.exists() {
+        eprintln!("Skipping day {day}: no input at {}", path.display());
+        return Ok(());
+    }
+
+    let input = aoc_core::input::read(&path).context(InputSnafu)?;
+    let answers = Answers::load(&aoc::answers_path(day)).context(AnswersSnafu)?;
+    let solvers = days::solvers(day).context(UnknownDaySnafu { day })?;
+
+    for part in [1, 2] {
+        let Some(expected) = answers.accepted(part) else {
+            continue;
+        };
+
+        if let Some(warning) = answers.check(part, expected) {
+            panic!("Day {day}, part {part}: the accepted answer {expected} {warning}");
+        }
+
+        let Some(solver) = solvers[usize::from(part - 1)] else {
+            eprintln!("Skipping day {day}, part {part}: not solved");
+            continue;
+        };
+
+        let actual = solver(&input).context(SolveSnafu { day, part })?;
+        assert_eq!(expected, actual, "Day {day}, part {part}");
+    }
+
+    Ok(())
+}
+
+macro_rules! answer_tests {
+    ($($(#[$attr:meta])* $name:ident => $day:literal,)*) => {
+        $(
+            #[test]
+            $(#[$attr])*
+            #[snafu::report]
+            fn $name() -> Result<(), Error> {
+                check($day)
+            }
+        )*
+    };
+}
+
+answer_tests! {
+    day_01 => 1,
+    day_02 => 2,
+    day_03 => 3,
+    day_04 => 4,
+    day_05 => 5,
+    day_06 => 6,
+    day_07 => 7,
+    day_08 => 8,
+    day_09 => 9,
+    day_10 => 10,
+    day_11 => 11,
+    day_12 => 12,
+    day_13 => 13,
+    day_14 => 14,
+    day_15 => 15,
+    day_16 => 16,
+    day_17 => 17,
+    day_18 => 18,
+    day_19 => 19,
+    day_20 => 20,
+    day_21 => 21,
+    day_22 => 22,
+    #[ignore = "takes minutes without optimizations"]
+    day_23 => 23,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Day {day} does not exist"))]
+    UnknownDay { day: u8 },
+
+    #[snafu(display("Could not read the puzzle input"))]
+    Input { source: aoc_core::input::Error },
+
+    #[snafu(display("Could not read the accepted answers"))]
+    Answers { source: answers::Error },
+
+    #[snafu(display("Day {day}, part {part} could not be solved"))]
+    Solve {
+        source: days::BoxError,
+        day: u8,
+        part: u8,
+    },
+}
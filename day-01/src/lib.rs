@@ -0,0 +1,215 @@
+use aho_corasick::{AhoCorasick, Match};
+use aoc_core::Solution;
+use snafu::prelude::*;
+use std::cmp;
+
+/// Day 1: Trebuchet?!
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input<'a> = Vec<&'a str>;
+    type Error = CalibrationError;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        calibration_sum(input, Mode::Digits)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        calibration_sum(input, Mode::DigitsAndWords)
+    }
+}
+
+/// Each line of the calibration document.
+pub fn parse_input(s: &str) -> Vec<&str> {
+    s.lines().collect()
+}
+
+/// The digits as written with numerals.
+pub const NUMERALS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+/// The digits spelled out in English.
+pub const ENGLISH: [(&str, u32); 10] = [
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// Which spellings of a digit count towards the calibration value.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// Only numerals, as in part 1.
+    Digits,
+    /// Numerals and English words, as in part 2.
+    DigitsAndWords,
+}
+
+impl Mode {
+    pub fn matcher(self) -> Result<DigitMatcher, BuildMatcherError> {
+        match self {
+            Mode::Digits => DigitMatcher::new(NUMERALS),
+            Mode::DigitsAndWords => DigitMatcher::new(NUMERALS.into_iter().chain(ENGLISH)),
+        }
+    }
+}
+
+pub fn calibration_sum(lines: &[&str], mode: Mode) -> Result<u32, CalibrationError> {
+    calibration_sum_with(lines, &mode.matcher()?)
+}
+
+/// Sums the calibration value of each line, recognizing digits
+/// using the given matcher.
+pub fn calibration_sum_with(
+    lines: &[&str],
+    matcher: &DigitMatcher,
+) -> Result<u32, CalibrationError> {
+    lines
+        .iter()
+        .map(|&line| {
+            let (first, last) = matcher
+                .first_and_last(line)
+                .context(NoDigitsSnafu { line })?;
+
+            Ok(first * 10 + last)
+        })
+        .sum()
+}
+
+/// Finds every spelling of a digit in a line in a single pass,
+/// including spellings that overlap, such as the `two` in `eightwo`.
+#[derive(Debug, Clone)]
+pub struct DigitMatcher {
+    automaton: AhoCorasick,
+    values: Vec<u32>,
+}
+
+impl DigitMatcher {
+    /// Builds a matcher from pairs of spelling and the digit it
+    /// stands for.
+    pub fn new<S>(vocabulary: impl IntoIterator<Item = (S, u32)>) -> Result<Self, BuildMatcherError>
+    where
+        S: AsRef<[u8]>,
+    {
+        let (spellings, values): (Vec<_>, Vec<_>) = vocabulary.into_iter().unzip();
+        let automaton = AhoCorasick::new(spellings).context(BuildMatcherSnafu)?;
+
+        Ok(Self { automaton, values })
+    }
+
+    /// The digits that start earliest and latest in the line.
+    pub fn first_and_last(&self, line: &str) -> Option<(u32, u32)> {
+        let mut matches = self.automaton.find_overlapping_iter(line);
+
+        let first = matches.next()?;
+        let (first, last) = matches.fold((first, first), |(first, last), m| {
+            (
+                cmp::min_by_key(first, m, |m| m.start()),
+                cmp::max_by_key(last, m, |m| m.start()),
+            )
+        });
+
+        let value = |m: Match| self.values[m.pattern()];
+        Some((value(first), value(last)))
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Could not build a matcher from the vocabulary"))]
+pub struct BuildMatcherError {
+    source: aho_corasick::BuildError,
+}
+
+#[derive(Debug, Snafu)]
+pub enum CalibrationError {
+    #[snafu(display("There were no digits in '{line}'"))]
+    NoDigits { line: String },
+
+    #[snafu(context(false))]
+    Matcher { source: BuildMatcherError },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+    const EXAMPLE_INPUT_2: &str = include_str!("../example-input-2");
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), CalibrationError> {
+        let lines = parse_input(EXAMPLE_INPUT_1);
+        assert_eq!(142, calibration_sum(&lines, Mode::Digits)?);
+        assert_eq!(142, calibration_sum(&lines, Mode::DigitsAndWords)?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2() -> Result<(), CalibrationError> {
+        assert_eq!(
+            281,
+            calibration_sum(&parse_input(EXAMPLE_INPUT_2), Mode::DigitsAndWords)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn overlapping_words() -> Result<(), CalibrationError> {
+        assert_eq!(82, calibration_sum(&["eightwo"], Mode::DigitsAndWords)?);
+        assert_eq!(18, calibration_sum(&["oneight"], Mode::DigitsAndWords)?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn numerals_ignore_words() -> Result<(), CalibrationError> {
+        let matcher = DigitMatcher::new(NUMERALS)?;
+        assert_eq!(
+            142,
+            calibration_sum_with(&parse_input(EXAMPLE_INPUT_1), &matcher)?
+        );
+        assert_eq!(33, calibration_sum_with(&["one3eight"], &matcher)?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn other_vocabularies() -> Result<(), CalibrationError> {
+        let german = [("eins", 1), ("zwei", 2), ("drei", 3), ("acht", 8)];
+        let matcher = DigitMatcher::new(german)?;
+        assert_eq!(83, calibration_sum_with(&["achtzweidrei"], &matcher)?);
+        assert_eq!(13, calibration_sum_with(&["xeins7drei"], &matcher)?);
+        assert!(calibration_sum_with(&["seven"], &matcher).is_err());
+
+        Ok(())
+    }
+}
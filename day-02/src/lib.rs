@@ -0,0 +1,355 @@
+use aoc_core::Solution;
+use snafu::prelude::*;
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+pub type GameId = u64;
+
+/// The cubes the Elf loads into the bag in part 1.
+pub const PUZZLE_BAG: [(&str, u64); 3] = [("red", 12), ("green", 13), ("blue", 14)];
+
+#[derive(Debug, Clone)]
+pub struct Game {
+    pub id: GameId,
+    draws: Vec<Draw>,
+}
+
+impl Game {
+    pub fn draws(&self) -> &[Draw] {
+        &self.draws
+    }
+
+    /// Checks every draw against the bag, noting each color that
+    /// exceeds what the bag holds.
+    pub fn report(&self, bag: &Draw) -> GameReport {
+        let violations = self
+            .draws
+            .iter()
+            .enumerate()
+            .flat_map(|(draw, d)| {
+                d.iter().filter_map(move |(color, drawn)| {
+                    let limit = bag.get(color);
+                    (drawn > limit).then(|| Violation {
+                        draw,
+                        color: color.to_owned(),
+                        drawn,
+                        limit,
+                    })
+                })
+            })
+            .collect();
+
+        GameReport {
+            id: self.id,
+            violations,
+        }
+    }
+
+    /// The fewest cubes of each color that could have produced every
+    /// draw. Colors never drawn are left out.
+    pub fn minimum_cubes(&self) -> Draw {
+        let mut minimum = Draw::default();
+
+        for draw in &self.draws {
+            for (color, count) in draw.iter() {
+                let entry = minimum.0.entry(color.to_owned()).or_default();
+                *entry = (*entry).max(count);
+            }
+        }
+
+        minimum
+    }
+}
+
+/// Day 2: Cube Conundrum
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input<'a> = Vec<Game>;
+    type Error = Error;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        Ok(sum_of_possible_game_ids(
+            input,
+            &Draw::from_iter(PUZZLE_BAG),
+        ))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        Ok(sum_of_power_of_minimum_cubes(input))
+    }
+}
+
+pub fn sum_of_possible_game_ids(games: &[Game], bag: &Draw) -> u64 {
+    games
+        .iter()
+        .map(|game| game.report(bag))
+        .filter(GameReport::is_possible)
+        .map(|report| report.id)
+        .sum()
+}
+
+pub fn sum_of_power_of_minimum_cubes(games: &[Game]) -> u64 {
+    games.iter().map(|game| game.minimum_cubes().power()).sum()
+}
+
+/// Whether a game could have been played with a given bag, and if
+/// not, which draws took out more cubes than the bag holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameReport {
+    pub id: GameId,
+    pub violations: Vec<Violation>,
+}
+
+impl GameReport {
+    pub fn is_possible(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl fmt::Display for GameReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { id, violations } = self;
+
+        if violations.is_empty() {
+            return write!(f, "Game {id} is possible");
+        }
+
+        write!(f, "Game {id} is impossible")?;
+        for (i, violation) in violations.iter().enumerate() {
+            let sep = if i == 0 { ": " } else { "; " };
+            write!(f, "{sep}{violation}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// One color of one draw that exceeded the bag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// The zero-based position of the draw within the game.
+    pub draw: usize,
+    pub color: String,
+    pub drawn: u64,
+    pub limit: u64,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            draw,
+            color,
+            drawn,
+            limit,
+        } = self;
+        write!(
+            f,
+            "draw {} took {drawn} {color} but the bag holds {limit}",
+            draw + 1
+        )
+    }
+}
+
+pub fn parse_input(s: &str) -> Result<Vec<Game>, Error> {
+    s.lines()
+        .map(|line| {
+            let mut parts = line.splitn(2, ':');
+            let id = parts.next().context(MissingIdSnafu { line })?;
+            let draws = parts.next().context(MissingDrawsSnafu { line })?;
+
+            let id = id.trim_start_matches("Game ");
+            let id = id.parse().context(InvalidIdSnafu { line, id })?;
+
+            let draws = draws
+                .split(';')
+                .map(|draw| Draw::from_str(draw).context(InvalidDrawSnafu { line, draw }))
+                .collect::<Result<_, _>>()?;
+
+            Ok(Game { id, draws })
+        })
+        .collect()
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display(r#"The line "{line}" had no game ID"#))]
+    MissingId { line: String },
+
+    #[snafu(display(r#"The line "{line}" had no color draws"#))]
+    MissingDraws { line: String },
+
+    #[snafu(display(r#"The line "{line}" had an invalid game ID "{id}""#))]
+    InvalidId {
+        source: std::num::ParseIntError,
+        line: String,
+        id: String,
+    },
+
+    #[snafu(display(r#"The line "{line}" had an invalid color draw "{draw}""#))]
+    InvalidDraw {
+        source: ParseDrawError,
+        line: String,
+        draw: String,
+    },
+}
+
+/// How many cubes of each color were shown at once. Also used to
+/// describe what a bag holds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Draw(BTreeMap<String, u64>);
+
+impl Draw {
+    /// The count for a color, which is zero if it never appeared.
+    pub fn get(&self, color: &str) -> u64 {
+        self.0.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> + '_ {
+        self.0.iter().map(|(color, &count)| (color.as_str(), count))
+    }
+
+    /// The product of the count of every color present.
+    pub fn power(&self) -> u64 {
+        self.0.values().product()
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, u64)> for Draw {
+    fn from_iter<I: IntoIterator<Item = (S, u64)>>(iter: I) -> Self {
+        Self(iter.into_iter().map(|(c, n)| (c.into(), n)).collect())
+    }
+}
+
+impl FromStr for Draw {
+    type Err = ParseDrawError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use parse_draw_error::*;
+
+        let mut this = BTreeMap::new();
+
+        for component in s.split(',') {
+            let mut parts = component.trim().splitn(2, ' ');
+
+            let count = parts.next().context(MissingCountSnafu { component })?;
+            let color = parts
+                .next()
+                .filter(|c| !c.is_empty())
+                .context(MissingColorSnafu { component })?;
+
+            let count = count
+                .parse()
+                .context(InvalidCountSnafu { component, count })?;
+
+            if this.insert(color.to_owned(), count).is_some() {
+                return DuplicateColorSnafu { component, color }.fail();
+            }
+        }
+
+        Ok(Self(this))
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum ParseDrawError {
+    #[snafu(display(r#"The component "{component}" was missing the count"#))]
+    MissingCount { component: String },
+
+    #[snafu(display(r#"The component "{component}" was missing the color"#))]
+    MissingColor { component: String },
+
+    #[snafu(display(r#"The component "{component}" had an invalid count "{count}""#))]
+    InvalidCount {
+        source: std::num::ParseIntError,
+        component: String,
+        count: String,
+    },
+
+    #[snafu(display(r#"The component "{component}" repeated the color "{color}""#))]
+    DuplicateColor { component: String, color: String },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), Error> {
+        let games = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(
+            8,
+            sum_of_possible_game_ids(&games, &Draw::from_iter(PUZZLE_BAG))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2() -> Result<(), Error> {
+        let games = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(2286, sum_of_power_of_minimum_cubes(&games));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn report_names_the_broken_limits() -> Result<(), Error> {
+        let games = parse_input(EXAMPLE_INPUT_1)?;
+        let bag = Draw::from_iter(PUZZLE_BAG);
+
+        let report = games[3].report(&bag);
+        assert_eq!(
+            report.violations,
+            [
+                Violation {
+                    draw: 2,
+                    color: "blue".into(),
+                    drawn: 15,
+                    limit: 14
+                },
+                Violation {
+                    draw: 2,
+                    color: "red".into(),
+                    drawn: 14,
+                    limit: 12
+                },
+            ],
+        );
+        assert_eq!(
+            "Game 4 is impossible: draw 3 took 15 blue but the bag holds 14; \
+             draw 3 took 14 red but the bag holds 12",
+            report.to_string(),
+        );
+        assert!(games[0].report(&bag).is_possible());
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn any_colors() -> Result<(), Error> {
+        let games = parse_input("Game 7: 3 yellow, 1 red; 5 yellow\nGame 9: 2 purple")?;
+
+        let bag = Draw::from_iter([("yellow", 4), ("red", 1)]);
+        assert_eq!(0, sum_of_possible_game_ids(&games, &bag));
+
+        let bag = Draw::from_iter([("yellow", 5), ("red", 1)]);
+        assert_eq!(7, sum_of_possible_game_ids(&games, &bag));
+
+        assert_eq!(5 + 2, sum_of_power_of_minimum_cubes(&games));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,372 @@
+use aoc_core::Solution;
+use grid::{Coord, Grid};
+use itertools::Itertools;
+use snafu::prelude::*;
+use std::{convert::Infallible, ops};
+
+/// Identifies one number in a schematic, no matter how many cells it
+/// covers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NumberId(usize);
+
+/// Identifies one symbol in a schematic.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SymbolId(usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartNumber {
+    pub id: NumberId,
+    pub value: u64,
+    pub row: usize,
+    /// The columns covered by the digits.
+    pub columns: ops::Range<usize>,
+}
+
+impl PartNumber {
+    pub fn coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.columns.clone().map(|x| (x, self.row))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub id: SymbolId,
+    pub symbol: char,
+    pub coord: Coord,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Component {
+    Number(NumberId),
+    Symbol(SymbolId),
+}
+
+/// The numbers and symbols of an engine schematic, along with which
+/// cells each of them occupy.
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    numbers: Vec<PartNumber>,
+    symbols: Vec<Symbol>,
+    cells: Grid<Option<Component>>,
+}
+
+impl Schematic {
+    pub fn numbers(&self) -> &[PartNumber] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// Every occurrence of the given symbol.
+    pub fn symbols_of(&self, symbol: char) -> impl Iterator<Item = &Symbol> + '_ {
+        self.symbols.iter().filter(move |s| s.symbol == symbol)
+    }
+
+    /// The numbers touching the symbol, including diagonally, in
+    /// reading order.
+    pub fn numbers_adjacent_to(&self, symbol: SymbolId) -> Vec<NumberId> {
+        let coord = self[symbol].coord;
+
+        self.cells
+            .neighbors_diagonal(coord)
+            .flat_map(|pos| match self.cells[pos] {
+                Some(Component::Number(id)) => Some(id),
+                _ => None,
+            })
+            .sorted()
+            .dedup()
+            .collect()
+    }
+
+    /// The symbols touching any digit of the number, including
+    /// diagonally, in reading order.
+    pub fn symbols_adjacent_to(&self, number: NumberId) -> Vec<SymbolId> {
+        self[number]
+            .coords()
+            .flat_map(|coord| self.cells.neighbors_diagonal(coord))
+            .flat_map(|pos| match self.cells[pos] {
+                Some(Component::Symbol(id)) => Some(id),
+                _ => None,
+            })
+            .sorted()
+            .dedup()
+            .collect()
+    }
+
+    /// The numbers touching at least one symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &PartNumber> + '_ {
+        self.numbers
+            .iter()
+            .filter(|n| !self.symbols_adjacent_to(n.id).is_empty())
+    }
+
+    /// Every symbol that counts as a gear under the rule, in reading
+    /// order.
+    pub fn gears<'a>(&'a self, rule: &'a GearRule<'_>) -> impl Iterator<Item = Gear> + 'a {
+        self.symbols
+            .iter()
+            .filter(|s| rule.symbols.contains(&s.symbol))
+            .flat_map(|s| {
+                let numbers = self.numbers_adjacent_to(s.id);
+                if !rule.neighbors.contains(&numbers.len()) {
+                    return None;
+                }
+
+                let values = numbers.iter().map(|&id| self[id].value);
+                let value = rule.aggregate.apply(values);
+
+                Some(Gear {
+                    symbol: s.id,
+                    coord: s.coord,
+                    numbers,
+                    value,
+                })
+            })
+    }
+}
+
+/// Decides which symbols are gears and how their numbers combine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearRule<'a> {
+    pub symbols: &'a [char],
+    /// How many distinct numbers must touch the symbol.
+    pub neighbors: ops::RangeInclusive<usize>,
+    pub aggregate: Aggregate,
+}
+
+/// The puzzle's gears: a `*` touching exactly two numbers, whose ratio
+/// is their product.
+pub const GEAR_RATIO: GearRule<'static> = GearRule {
+    symbols: &['*'],
+    neighbors: 2..=2,
+    aggregate: Aggregate::Product,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Aggregate {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Aggregate {
+    fn apply(self, values: impl Iterator<Item = u64>) -> u64 {
+        match self {
+            Aggregate::Product => values.product(),
+            Aggregate::Sum => values.sum(),
+            Aggregate::Max => values.max().unwrap_or(0),
+        }
+    }
+}
+
+/// A symbol matching a gear rule, with its combined value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gear {
+    pub symbol: SymbolId,
+    pub coord: Coord,
+    pub numbers: Vec<NumberId>,
+    pub value: u64,
+}
+
+impl ops::Index<NumberId> for Schematic {
+    type Output = PartNumber;
+
+    fn index(&self, id: NumberId) -> &Self::Output {
+        &self.numbers[id.0]
+    }
+}
+
+impl ops::Index<SymbolId> for Schematic {
+    type Output = Symbol;
+
+    fn index(&self, id: SymbolId) -> &Self::Output {
+        &self.symbols[id.0]
+    }
+}
+
+/// Day 3: Gear Ratios
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input<'a> = Schematic;
+    type Error = ParseSchematicError;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        Ok(sum_of_part_numbers(input))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        Ok(sum_of_gear_ratios(input))
+    }
+}
+
+pub fn sum_of_part_numbers(schematic: &Schematic) -> u64 {
+    schematic.part_numbers().map(|n| n.value).sum()
+}
+
+pub fn sum_of_gear_ratios(schematic: &Schematic) -> u64 {
+    schematic.gears(&GEAR_RATIO).map(|gear| gear.value).sum()
+}
+
+pub fn parse_input(s: &str) -> Result<Schematic, ParseSchematicError> {
+    let chars = Grid::parse(s, Ok::<_, Infallible>).context(GridSnafu)?;
+    let mut cells = Grid::new(chars.width(), chars.height(), None);
+    let mut numbers = vec![];
+    let mut symbols = vec![];
+
+    for (y, row) in chars.rows().enumerate() {
+        let runs = row.iter().enumerate().group_by(|(_, c)| c.is_ascii_digit());
+
+        for (is_number, run) in &runs {
+            if is_number {
+                let (xs, num): (Vec<_>, String) = run.unzip();
+                let value = num.parse().context(InvalidNumberSnafu { num })?;
+                let id = NumberId(numbers.len());
+
+                for &x in &xs {
+                    cells[(x, y)] = Some(Component::Number(id));
+                }
+
+                let columns = xs[0]..xs[xs.len() - 1] + 1;
+                numbers.push(PartNumber {
+                    id,
+                    value,
+                    row: y,
+                    columns,
+                });
+            } else {
+                for (x, &c) in run {
+                    match c {
+                        '.' => { /* blank space */ }
+                        symbol => {
+                            let id = SymbolId(symbols.len());
+                            let coord = (x, y);
+                            cells[coord] = Some(Component::Symbol(id));
+                            symbols.push(Symbol { id, symbol, coord });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(Schematic {
+        numbers,
+        symbols,
+        cells,
+    })
+}
+
+#[derive(Debug, Snafu)]
+pub enum ParseSchematicError {
+    InvalidNumber {
+        source: std::num::ParseIntError,
+        num: String,
+    },
+
+    Grid {
+        source: grid::ParseError<Infallible>,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), ParseSchematicError> {
+        let schematic = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(4361, sum_of_part_numbers(&schematic));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2() -> Result<(), ParseSchematicError> {
+        let schematic = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(467835, sum_of_gear_ratios(&schematic));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn equal_numbers_stay_distinct() -> Result<(), ParseSchematicError> {
+        let schematic = parse_input("12.12\n..*..\n12...")?;
+        let star = schematic.symbols_of('*').next().unwrap();
+
+        let values = schematic
+            .numbers_adjacent_to(star.id)
+            .into_iter()
+            .map(|id| schematic[id].value)
+            .collect::<Vec<_>>();
+        assert_eq!([12, 12, 12], &*values);
+        assert_eq!(0, schematic.gears(&GEAR_RATIO).count());
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn adjacency_in_both_directions() -> Result<(), ParseSchematicError> {
+        let schematic = parse_input(EXAMPLE_INPUT_1)?;
+
+        let n467 = &schematic.numbers()[0];
+        assert_eq!((467, 0, 0..3), (n467.value, n467.row, n467.columns.clone()));
+
+        let symbols = schematic.symbols_adjacent_to(n467.id);
+        assert_eq!(
+            ['*'],
+            &*symbols
+                .iter()
+                .map(|&s| schematic[s].symbol)
+                .collect::<Vec<_>>()
+        );
+
+        let n114 = &schematic.numbers()[1];
+        assert_eq!(114, n114.value);
+        assert!(schematic.symbols_adjacent_to(n114.id).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn custom_gear_rules() -> Result<(), ParseSchematicError> {
+        let schematic = parse_input("12.12\n..*..\n12.#3\n....4")?;
+
+        let triple = GearRule {
+            symbols: &['*', '#'],
+            neighbors: 3..=3,
+            aggregate: Aggregate::Sum,
+        };
+        let gears = schematic.gears(&triple).collect::<Vec<_>>();
+        assert_eq!(1, gears.len());
+        assert_eq!(((2, 1), 36), (gears[0].coord, gears[0].value));
+
+        let any = GearRule {
+            symbols: &['*', '#'],
+            neighbors: 1..=usize::MAX,
+            aggregate: Aggregate::Max,
+        };
+        let gears = schematic
+            .gears(&any)
+            .map(|g| (g.coord, g.numbers.len(), g.value))
+            .collect::<Vec<_>>();
+        assert_eq!([((2, 1), 3, 12), ((3, 2), 2, 4)], &*gears);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,520 @@
+use aoc_core::Solution;
+use snafu::prelude::*;
+use std::{
+    borrow::Borrow,
+    collections::{HashSet, VecDeque},
+    hash::Hash,
+    io::BufRead,
+    marker::PhantomData,
+    num::ParseIntError,
+    str::FromStr,
+};
+
+/// An integer type that can be printed on a card. Pick one wide
+/// enough for the deck; the puzzle's cards fit in a `u8`.
+pub trait Num: Copy + Eq + Hash + FromStr<Err = ParseIntError> + TryInto<usize> {}
+
+impl<T> Num for T where T: Copy + Eq + Hash + FromStr<Err = ParseIntError> + TryInto<usize> {}
+
+pub type CardId = u32;
+
+/// Day 4: Scratchcards
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input<'a> = Vec<Card>;
+    type Error = Error;
+    type Part1 = u64;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        sum_of_winning_points(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        number_of_scratchcards(input)
+    }
+}
+
+pub fn sum_of_winning_points<N: Num>(cards: &[Card<N>]) -> Result<u64, Error> {
+    add_points(cards.iter().map(Card::points))
+}
+
+/// Like [`sum_of_winning_points`], but for cards that are still being
+/// parsed.
+pub fn try_sum_of_winning_points<N, I>(cards: I) -> Result<u64, Error>
+where
+    N: Num,
+    I: IntoIterator<Item = Result<Card<N>, ParseError>>,
+{
+    add_points(cards.into_iter().map(|card| card?.points()))
+}
+
+/// Both answers from a single pass over cards that are still being
+/// parsed, for piles that can only be read once.
+pub fn try_points_and_scratchcards<N, I>(cards: I) -> Result<(u64, usize), Error>
+where
+    N: Num,
+    I: IntoIterator<Item = Result<Card<N>, ParseError>>,
+{
+    let mut tallier = Tallier::default();
+    let mut number = 0usize;
+
+    let sum = add_points(cards.into_iter().map(|card| {
+        let card = card?;
+        number += tallier.tally(&card)?.copies;
+        card.points()
+    }))?;
+
+    Ok((sum, number))
+}
+
+fn add_points(mut points: impl Iterator<Item = Result<u64, Error>>) -> Result<u64, Error> {
+    points.try_fold(0u64, |sum, points| {
+        sum.checked_add(points?).context(PointsOverflowSnafu)
+    })
+}
+
+pub fn number_of_scratchcards<N: Num>(cards: &[Card<N>]) -> Result<usize, Error> {
+    cascade(cards).map(|tally| Ok(tally?.copies)).sum()
+}
+
+/// Wins copies of later cards one card at a time, holding only the
+/// copies still owed to the next few cards rather than a count for
+/// the whole pile.
+pub fn cascade<N, I>(cards: I) -> Cascade<I::IntoIter, N>
+where
+    N: Num,
+    I: IntoIterator,
+    I::Item: Borrow<Card<N>>,
+{
+    Cascade {
+        cards: cards.into_iter(),
+        tallier: Tallier::default(),
+        number: PhantomData,
+    }
+}
+
+/// Like [`cascade`], but for cards that are still being parsed, such
+/// as those from [`parse_cards`]. A card that fails to parse is
+/// reported in its place.
+pub fn try_cascade<N, I>(cards: I) -> impl Iterator<Item = Result<CardTally, Error>>
+where
+    N: Num,
+    I: IntoIterator<Item = Result<Card<N>, ParseError>>,
+{
+    let mut tallier = Tallier::default();
+    cards.into_iter().map(move |card| tallier.tally(&card?))
+}
+
+/// How many copies of a card ended up being scratched.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CardTally {
+    pub id: CardId,
+    pub matches: usize,
+    /// Includes the original card.
+    pub copies: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Cascade<I, N = u32> {
+    cards: I,
+    tallier: Tallier,
+    number: PhantomData<fn() -> N>,
+}
+
+impl<I, N> Iterator for Cascade<I, N>
+where
+    N: Num,
+    I: Iterator,
+    I::Item: Borrow<Card<N>>,
+{
+    type Item = Result<CardTally, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let card = self.cards.next()?;
+        Some(self.tallier.tally(card.borrow()))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Tallier {
+    previous: Option<CardId>,
+    /// Extra copies won for the upcoming cards, nearest first.
+    pending: VecDeque<usize>,
+}
+
+impl Tallier {
+    fn tally<N: Num>(&mut self, card: &Card<N>) -> Result<CardTally, Error> {
+        if let Some(previous) = self.previous {
+            ensure!(
+                previous.checked_add(1) == Some(card.id),
+                OutOfSequenceSnafu {
+                    previous,
+                    actual: card.id,
+                }
+            );
+        }
+        self.previous = Some(card.id);
+
+        let matches = card.n_matches();
+        let copies = 1 + self.pending.pop_front().unwrap_or(0);
+
+        if self.pending.len() < matches {
+            self.pending.resize(matches, 0);
+        }
+        for pending in self.pending.iter_mut().take(matches) {
+            *pending += copies;
+        }
+
+        Ok(CardTally {
+            id: card.id,
+            matches,
+            copies,
+        })
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(context(false))]
+    Parse { source: ParseError },
+
+    #[snafu(display("Card {actual} came after card {previous}"))]
+    OutOfSequence { previous: CardId, actual: CardId },
+
+    #[snafu(display("Card {id} has {matches} matches, too many points to count"))]
+    TooManyMatches { id: CardId, matches: usize },
+
+    #[snafu(display("The cards are worth too many points to count"))]
+    PointsOverflow,
+}
+
+#[derive(Debug, Clone)]
+pub struct Card<N = u32> {
+    pub id: CardId,
+    winners: Winners<N>,
+    numbers: Vec<N>,
+}
+
+impl<N: Num> Card<N> {
+    /// One point for the first match, doubled for each match after.
+    pub fn points(&self) -> Result<u64, Error> {
+        let n_matches = self.n_matches();
+
+        if n_matches == 0 {
+            return Ok(0);
+        }
+
+        u32::try_from(n_matches - 1)
+            .ok()
+            .and_then(|shift| 1u64.checked_shl(shift))
+            .context(TooManyMatchesSnafu {
+                id: self.id,
+                matches: n_matches,
+            })
+    }
+
+    pub fn n_matches(&self) -> usize {
+        self.numbers
+            .iter()
+            .filter(|&&n| self.winners.contains(n))
+            .count()
+    }
+}
+
+/// The winning numbers of a card, kept as a bitset when they are all
+/// small enough and as a hash set otherwise.
+#[derive(Debug, Clone)]
+enum Winners<N> {
+    Small([u64; SMALL_WORDS]),
+    Large(HashSet<N>),
+}
+
+const SMALL_WORDS: usize = 4;
+
+/// Where `n` falls in the bitset, if it is small enough to fit.
+fn small_index<N: Num>(n: N) -> Option<usize> {
+    n.try_into().ok().filter(|&idx| idx < SMALL_WORDS * 64)
+}
+
+fn bit(idx: usize) -> u64 {
+    1 << (idx % 64)
+}
+
+impl<N: Num> Winners<N> {
+    fn contains(&self, n: N) -> bool {
+        match self {
+            Self::Small(bits) => small_index(n).is_some_and(|idx| bits[idx / 64] & bit(idx) != 0),
+            Self::Large(set) => set.contains(&n),
+        }
+    }
+}
+
+impl<N: Num> FromIterator<N> for Winners<N> {
+    fn from_iter<I: IntoIterator<Item = N>>(iter: I) -> Self {
+        let winners = iter.into_iter().collect::<Vec<_>>();
+        let indices = winners
+            .iter()
+            .map(|&n| small_index(n))
+            .collect::<Option<Vec<_>>>();
+
+        match indices {
+            Some(indices) => {
+                let mut bits = [0; SMALL_WORDS];
+                for idx in indices {
+                    bits[idx / 64] |= bit(idx);
+                }
+                Self::Small(bits)
+            }
+            None => Self::Large(winners.into_iter().collect()),
+        }
+    }
+}
+
+pub fn parse_input(s: &str) -> Result<Vec<Card>, ParseError> {
+    parse_cards(s).collect()
+}
+
+/// Parses one card per line of text that is already in memory.
+pub fn parse_cards(s: &str) -> impl Iterator<Item = Result<Card, ParseError>> + '_ {
+    parse_cards_as(s)
+}
+
+/// Like [`parse_cards`], with the card numbers read as `N`.
+pub fn parse_cards_as<'a, N: Num + 'a>(
+    s: &'a str,
+) -> impl Iterator<Item = Result<Card<N>, ParseError>> + 'a {
+    s.lines().map(parse_card)
+}
+
+/// Parses one card per line as they are read, so only the current
+/// line is ever held in memory.
+pub fn parse_cards_from(reader: impl BufRead) -> impl Iterator<Item = Result<Card, ParseError>> {
+    reader
+        .lines()
+        .map(|line| parse_card(&line.context(ReadSnafu)?))
+}
+
+/// Parses a single line such as `Card 1: 41 48 | 83 86 17`.
+pub fn parse_card<N: Num>(line: &str) -> Result<Card<N>, ParseError> {
+    let mut parts = line.splitn(3, &[':', '|']);
+
+    let id = parts.next().context(MissingIdSnafu { line })?;
+    let winners = parts.next().context(MissingWinnersSnafu { line })?;
+    let numbers = parts.next().context(MissingNumbersSnafu { line })?;
+
+    let winners = winners
+        .split_ascii_whitespace()
+        .map(|winner| winner.parse::<N>().context(ParseWinnerSnafu { winner }))
+        .collect::<Result<_, _>>()
+        .context(InvalidWinnerSnafu { line })?;
+
+    let numbers = numbers
+        .split_ascii_whitespace()
+        .map(|number| number.parse::<N>().context(ParseNumberSnafu { number }))
+        .collect::<Result<_, _>>()
+        .context(InvalidNumberSnafu { line })?;
+
+    let id = id.trim_start_matches("Card").trim();
+    let id = id.parse().context(InvalidIdSnafu { line, id })?;
+
+    Ok(Card {
+        id,
+        winners,
+        numbers,
+    })
+}
+
+#[derive(Debug, Snafu)]
+pub enum ParseError {
+    Read {
+        source: std::io::Error,
+    },
+
+    MissingId {
+        line: String,
+    },
+
+    InvalidId {
+        source: std::num::ParseIntError,
+        line: String,
+        id: String,
+    },
+
+    MissingWinners {
+        line: String,
+    },
+
+    MissingNumbers {
+        line: String,
+    },
+
+    InvalidWinner {
+        source: ParseWinnerError,
+        line: String,
+    },
+
+    InvalidNumber {
+        source: ParseNumberError,
+        line: String,
+    },
+}
+
+#[derive(Debug, Snafu)]
+pub struct ParseWinnerError {
+    source: std::num::ParseIntError,
+    winner: String,
+}
+
+#[derive(Debug, Snafu)]
+pub struct ParseNumberError {
+    source: std::num::ParseIntError,
+    number: String,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), Error> {
+        let cards = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(13, sum_of_winning_points(&cards)?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2() -> Result<(), Error> {
+        let cards = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(30, number_of_scratchcards(&cards)?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn cascade_tallies_each_card() -> Result<(), Error> {
+        let tallies = try_cascade(parse_cards(EXAMPLE_INPUT_1))
+            .map(|t| t.map(|t| (t.id, t.matches, t.copies)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        assert_eq!(
+            [
+                (1, 4, 1),
+                (2, 2, 2),
+                (3, 2, 4),
+                (4, 1, 8),
+                (5, 0, 14),
+                (6, 0, 1)
+            ],
+            &*tallies,
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn cascade_rejects_gaps() -> Result<(), Error> {
+        let cards = parse_input("Card 1: 1 | 2\nCard 3: 1 | 1")?;
+        let err = number_of_scratchcards(&cards).unwrap_err();
+
+        assert!(matches!(
+            err,
+            Error::OutOfSequence {
+                previous: 1,
+                actual: 3
+            }
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn large_numbers() -> Result<(), ParseError> {
+        let card = parse_card::<u32>("Card 1: 5 300 10000 | 10000 5 299 300 7")?;
+        assert!(matches!(card.winners, Winners::Large(_)));
+        assert_eq!(3, card.n_matches());
+
+        let card = parse_card::<u32>("Card 1: 5 255 0 | 255 256 0 64 5")?;
+        assert!(matches!(card.winners, Winners::Small(_)));
+        assert_eq!(3, card.n_matches());
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn other_number_types() -> Result<(), Error> {
+        let cards = parse_cards_as::<u8>(EXAMPLE_INPUT_1).collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(13, sum_of_winning_points(&cards)?);
+        assert_eq!(30, number_of_scratchcards(&cards)?);
+
+        let card = parse_card::<u64>("Card 1: 18446744073709551615 3 | 3 18446744073709551615")?;
+        assert_eq!(2, card.points()?);
+
+        assert!(parse_cards_as::<u8>("Card 1: 256 | 1").all(|card| card.is_err()));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn too_many_matches() -> Result<(), ParseError> {
+        let winners = (1..=65)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let card = parse_card::<u32>(&format!("Card 7: {winners} | {winners}"))?;
+        assert!(matches!(
+            card.points(),
+            Err(Error::TooManyMatches { id: 7, matches: 65 })
+        ));
+
+        let card = parse_card::<u32>(&format!("Card 7: {winners} | {}", &winners[2..]))?;
+        assert_eq!(Some(1 << 63), card.points().ok());
+
+        let cards = parse_input(&format!(
+            "Card 1: {winners} | {0}\nCard 2: {winners} | {0}",
+            &winners[2..]
+        ))?;
+        assert!(matches!(
+            sum_of_winning_points(&cards),
+            Err(Error::PointsOverflow)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn one_pass_over_a_reader() -> Result<(), Error> {
+        let cards = parse_cards_from(EXAMPLE_INPUT_1.as_bytes());
+        assert_eq!((13, 30), try_points_and_scratchcards(cards)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn try_cascade_reports_parse_errors() {
+        let mut tallies = try_cascade(parse_cards("Card 1: 1 | 1\nCard 2: x | 1"));
+
+        assert!(matches!(
+            tallies.next(),
+            Some(Ok(CardTally { copies: 1, .. }))
+        ));
+        assert!(matches!(tallies.next(), Some(Err(Error::Parse { .. }))));
+    }
+}
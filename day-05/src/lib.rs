@@ -0,0 +1,666 @@
+use aoc_core::Solution;
+use snafu::prelude::*;
+use std::{
+    cmp,
+    collections::{BTreeMap, BTreeSet},
+    fmt, ops,
+};
+
+const SEED: &str = "seed";
+const LOCATION: &str = "location";
+
+/// Day 5: If You Give A Seed A Fertilizer
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input<'a> = Input<'a>;
+    type Error = Error;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        lowest_seed_location(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        lowest_seed_range_location(input)
+    }
+}
+
+pub fn lowest_seed_location(input: &Input<'_>) -> Result<u64, Error> {
+    let seed_to_location = input.path(SEED, LOCATION)?;
+
+    input
+        .seeds
+        .iter()
+        .map(|&seed| seed_to_location.get(seed))
+        .min()
+        .context(NoSeedsSnafu)
+}
+
+pub fn lowest_seed_range_location(input: &Input<'_>) -> Result<u64, Error> {
+    let seeds = input
+        .seeds
+        .chunks_exact(2)
+        .map(|range| range[0]..(range[0] + range[1]));
+
+    let seed_to_location = input.path(SEED, LOCATION)?;
+
+    seeds
+        .flat_map(|seeds| seed_to_location.get_range(seeds))
+        .map(|range| range.start)
+        .min()
+        .context(NoSeedsSnafu)
+}
+
+/// The seed that needs to be planted to end up at `location`.
+pub fn seed_for_location(input: &Input<'_>, location: u64) -> Result<u64, Error> {
+    let location_to_seed = input.path(SEED, LOCATION)?.invert().context(InvertSnafu)?;
+
+    Ok(location_to_seed.get(location))
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    // TODO[SNAFU]: transparent
+    #[snafu(context(false))]
+    Parsing {
+        source: ParseInputError,
+    },
+
+    NoSeeds,
+
+    Invert {
+        source: InvertMapError,
+    },
+
+    #[snafu(display("No stage of the almanac starts at '{category}'"))]
+    UnknownCategory {
+        category: String,
+    },
+
+    #[snafu(display("The almanac has no path from '{from}' to '{to}'"))]
+    NoPath {
+        from: String,
+        to: String,
+    },
+}
+
+pub struct Input<'a> {
+    seeds: Vec<u64>,
+    /// Ordered so that each stage starts where the previous one ends
+    stages: Vec<Stage<'a>>,
+}
+
+/// One `from-to-to map:` section of the almanac.
+pub struct Stage<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    map: Map,
+}
+
+impl Input<'_> {
+    /// Every stage between the two categories merged into one map.
+    pub fn path(&self, from: &str, to: &str) -> Result<Map, Error> {
+        let start = self
+            .stages
+            .iter()
+            .position(|stage| stage.from == from)
+            .context(UnknownCategorySnafu { category: from })?;
+
+        let mut composed = Map::default();
+
+        if from == to {
+            return Ok(composed);
+        }
+
+        for stage in &self.stages[start..] {
+            composed = composed.compose(&stage.map);
+
+            if stage.to == to {
+                return Ok(composed);
+            }
+        }
+
+        NoPathSnafu { from, to }.fail()
+    }
+}
+
+pub fn parse_input(s: &str) -> Result<Input<'_>, Error> {
+    Ok(parse_almanac(s)?)
+}
+
+fn parse_almanac(s: &str) -> Result<Input<'_>, ParseInputError> {
+    use parse_input_error::*;
+
+    let mut lines = s.lines().fuse();
+
+    let seeds = lines.next().context(MissingSeedsSnafu)?;
+    let mut seeds_parts = seeds.rsplitn(2, ':');
+    let seeds = seeds_parts
+        .next()
+        .context(MissingSeedValuesSnafu { seeds })?;
+    let seeds = seeds
+        .split_ascii_whitespace()
+        .map(|seed| seed.parse::<u64>().context(InvalidSeedSnafu { seed }))
+        .collect::<Result<_, _>>()?;
+
+    let mut stages = vec![];
+
+    while let Some(header) = lines.by_ref().find(|l| !l.trim().is_empty()) {
+        let (from, to) = header
+            .strip_suffix(" map:")
+            .and_then(|name| name.split_once("-to-"))
+            .context(InvalidHeaderSnafu { header })?;
+
+        let map = parse_map(&mut lines).context(InvalidMapSnafu { header })?;
+
+        stages.push(Stage { from, to, map });
+    }
+
+    let stages = chain_stages(stages)?;
+
+    Ok(Input { seeds, stages })
+}
+
+/// Puts the stages in order, checking that they form one unbroken
+/// chain from the first category to the last.
+fn chain_stages(stages: Vec<Stage<'_>>) -> Result<Vec<Stage<'_>>, ParseInputError> {
+    use parse_input_error::*;
+
+    let mut by_from = BTreeMap::new();
+    for stage in stages {
+        let from = stage.from;
+        let duplicate = by_from.insert(from, stage).is_some();
+        ensure!(!duplicate, DuplicateStageSnafu { category: from });
+    }
+
+    let tos = by_from
+        .values()
+        .map(|stage| stage.to)
+        .collect::<BTreeSet<_>>();
+    let mut firsts = by_from.keys().filter(|from| !tos.contains(*from));
+
+    let Some(&first) = firsts.next() else {
+        ensure!(by_from.is_empty(), CycleSnafu);
+        return Ok(vec![]);
+    };
+
+    if let Some(other) = firsts.next() {
+        return DisconnectedSnafu {
+            first,
+            other: *other,
+        }
+        .fail();
+    }
+
+    let mut chained = Vec::with_capacity(by_from.len());
+    let mut category = first;
+
+    while let Some(stage) = by_from.remove(category) {
+        category = stage.to;
+        chained.push(stage);
+    }
+
+    match by_from.into_keys().next() {
+        Some(category) => UnreachableSnafu { category }.fail(),
+        None => Ok(chained),
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum ParseInputError {
+    MissingSeeds,
+
+    MissingSeedValues {
+        seeds: String,
+    },
+
+    InvalidSeed {
+        source: std::num::ParseIntError,
+        seed: String,
+    },
+
+    #[snafu(display("'{header}' is not of the form 'X-to-Y map:'"))]
+    InvalidHeader {
+        header: String,
+    },
+
+    #[snafu(display("The values for '{header}' are invalid"))]
+    InvalidMap {
+        source: ParseMapError,
+        header: String,
+    },
+
+    #[snafu(display("More than one stage starts at '{category}'"))]
+    DuplicateStage {
+        category: String,
+    },
+
+    #[snafu(display("The stages starting at '{first}' and '{other}' are not connected"))]
+    Disconnected {
+        first: String,
+        other: String,
+    },
+
+    #[snafu(display("The stage starting at '{category}' can't be reached from the others"))]
+    Unreachable {
+        category: String,
+    },
+
+    #[snafu(display("The stages form a loop with no starting category"))]
+    Cycle,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Range {
+    start: u64,
+    length: u64,
+}
+
+impl Range {
+    fn end(&self) -> u64 {
+        self.start + self.length
+    }
+}
+
+/// A piecewise-linear mapping keyed by the start of each source range.
+/// Keys outside of every source range map to themselves.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Map(BTreeMap<u64, Range>);
+
+impl Map {
+    pub fn get(&self, key: u64) -> u64 {
+        let candidate_ascending = self.0.range(key..).next();
+        let candidate_descending = self.0.range(..key).next_back();
+
+        let candidates = [candidate_ascending, candidate_descending]
+            .into_iter()
+            .flatten();
+
+        for (&source_start, &destination_range) in candidates {
+            let source_end = source_start + destination_range.length;
+            if (source_start..source_end).contains(&key) {
+                let delta = key - source_start;
+                return destination_range.start + delta;
+            }
+        }
+
+        key
+    }
+
+    /// Maps every key in `keys` at once. The result is split wherever
+    /// `keys` crosses the edge of a mapping, and any keys that no
+    /// mapping covers pass through unchanged.
+    pub fn get_range(&self, keys: ops::Range<u64>) -> Vec<ops::Range<u64>> {
+        self.pieces(keys)
+            .into_iter()
+            .map(|(_, destination_range)| destination_range.start..destination_range.end())
+            .collect()
+    }
+
+    /// Splits `keys` at the edge of each mapping, pairing the start of
+    /// every piece with the range it maps to.
+    fn pieces(&self, keys: ops::Range<u64>) -> Vec<(u64, Range)> {
+        let mut mapped = vec![];
+        let mut start = keys.start;
+
+        let mut push = |start: u64, end: u64, destination_start: u64| {
+            let destination_range = Range {
+                start: destination_start,
+                length: end - start,
+            };
+            mapped.push((start, destination_range));
+        };
+
+        // A mapping starting before the keys may still overlap them
+        let first_source_start = self
+            .0
+            .range(..=start)
+            .next_back()
+            .map_or(start, |(&source_start, _)| source_start);
+
+        for (&source_start, &destination_range) in self.0.range(first_source_start..keys.end) {
+            let source_end = source_start + destination_range.length;
+            if source_end <= start {
+                continue;
+            }
+
+            if start < source_start {
+                push(start, source_start, start);
+                start = source_start;
+            }
+
+            let end = cmp::min(source_end, keys.end);
+            push(start, end, destination_range.start + (start - source_start));
+            start = end;
+        }
+
+        if start < keys.end {
+            push(start, keys.end, start);
+        }
+
+        mapped
+    }
+
+    /// The keys that no mapping covers.
+    fn gaps(&self) -> Vec<ops::Range<u64>> {
+        let mut gaps = vec![];
+        let mut start = 0;
+
+        for (&source_start, destination_range) in &self.0 {
+            if start < source_start {
+                gaps.push(start..source_start);
+            }
+            start = source_start + destination_range.length;
+        }
+
+        if start < u64::MAX {
+            gaps.push(start..u64::MAX);
+        }
+
+        gaps
+    }
+
+    /// A single map that gives the same result as looking a key up in
+    /// `self` and then looking that result up in `then`.
+    pub fn compose(&self, then: &Map) -> Map {
+        let mut composed = BTreeMap::new();
+
+        let mut insert = |source_start, destination_range: Range| {
+            // Anything not in the map is left alone anyway
+            if source_start != destination_range.start {
+                composed.insert(source_start, destination_range);
+            }
+        };
+
+        for (&source_start, destination_range) in &self.0 {
+            let middle = destination_range.start..destination_range.end();
+
+            for (middle_start, piece) in then.pieces(middle) {
+                insert(
+                    source_start + (middle_start - destination_range.start),
+                    piece,
+                );
+            }
+        }
+
+        for gap in self.gaps() {
+            for (start, piece) in then.pieces(gap) {
+                insert(start, piece);
+            }
+        }
+
+        Map(composed)
+    }
+
+    /// The map going from each destination back to its source. Only
+    /// possible when no two keys map to the same value.
+    pub fn invert(&self) -> Result<Map, InvertMapError> {
+        let sources = coverage(self.0.iter().map(|(&start, r)| start..start + r.length));
+        let destinations = coverage(self.0.values().map(|r| r.start..r.end()));
+        let destination_length = self.0.values().map(|r| r.length).sum::<u64>();
+        let covered_length = destinations.iter().map(|r| r.end - r.start).sum::<u64>();
+
+        ensure!(
+            sources == destinations && destination_length == covered_length,
+            InvertMapSnafu
+        );
+
+        let inverted = self
+            .0
+            .iter()
+            .map(|(&source_start, destination_range)| {
+                let source_range = Range {
+                    start: source_start,
+                    length: destination_range.length,
+                };
+                (destination_range.start, source_range)
+            })
+            .collect();
+
+        Ok(Map(inverted))
+    }
+}
+
+/// Prints one `destination source length` line per mapping, the same
+/// as in the almanac.
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (source_start, destination_range) in &self.0 {
+            let Range { start, length } = destination_range;
+            writeln!(f, "{start} {source_start} {length}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Merges the ranges into the sorted, non-overlapping set of values
+/// they cover.
+fn coverage(ranges: impl Iterator<Item = ops::Range<u64>>) -> Vec<ops::Range<u64>> {
+    let mut ranges = ranges.collect::<Vec<_>>();
+    ranges.sort_by_key(|r| r.start);
+
+    let mut merged: Vec<ops::Range<u64>> = vec![];
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = cmp::max(last.end, range.end),
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("More than one key maps to the same value"))]
+pub struct InvertMapError;
+
+fn parse_map<'a>(lines: impl IntoIterator<Item = &'a str>) -> Result<Map, ParseMapError> {
+    use parse_map_error::*;
+
+    let mut map = BTreeMap::new();
+
+    let lines = lines.into_iter().take_while(|l| !l.trim().is_empty());
+
+    for line in lines {
+        let mut parts = line.splitn(3, ' ');
+        let destination_start = parts.next().context(DestinationMissingSnafu { line })?;
+        let source_start = parts.next().context(SourceMissingSnafu { line })?;
+        let length = parts.next().context(LengthMissingSnafu { line })?;
+
+        let destination_start = destination_start
+            .parse()
+            .context(DestinationInvalidSnafu { line })?;
+        let source_start = source_start.parse().context(SourceInvalidSnafu { line })?;
+        let length = length.parse().context(LengthInvalidSnafu { line })?;
+
+        let destination_range = Range {
+            start: destination_start,
+            length,
+        };
+
+        map.insert(source_start, destination_range);
+    }
+
+    Ok(Map(map))
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum ParseMapError {
+    DestinationMissing {
+        line: String,
+    },
+
+    SourceMissing {
+        line: String,
+    },
+
+    LengthMissing {
+        line: String,
+    },
+
+    DestinationInvalid {
+        source: std::num::ParseIntError,
+        line: String,
+    },
+
+    SourceInvalid {
+        source: std::num::ParseIntError,
+        line: String,
+    },
+
+    LengthInvalid {
+        source: std::num::ParseIntError,
+        line: String,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), Error> {
+        let input = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(35, lowest_seed_location(&input)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2() -> Result<(), Error> {
+        let input = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(46, lowest_seed_range_location(&input)?);
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn composed_map_matches_each_stage() -> Result<(), Error> {
+        let input = parse_input(EXAMPLE_INPUT_1)?;
+        let seed_to_location = input.path(SEED, LOCATION)?;
+
+        for seed in 0..150 {
+            let location = input
+                .stages
+                .iter()
+                .fold(seed, |key, stage| stage.map.get(key));
+            assert_eq!(location, seed_to_location.get(seed), "Seed {seed}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_seed_for_location() -> Result<(), Error> {
+        let input = parse_input(EXAMPLE_INPUT_1)?;
+
+        assert_eq!(79, seed_for_location(&input, 82)?);
+        assert_eq!(82, seed_for_location(&input, 46)?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn path_between_any_categories() -> Result<(), Error> {
+        let input = parse_input(EXAMPLE_INPUT_1)?;
+
+        // Seed 79 has soil 81 and humidity 78
+        assert_eq!(78, input.path("soil", "humidity")?.get(81));
+        assert_eq!(81, input.path("soil", "soil")?.get(81));
+
+        assert!(matches!(
+            input.path("humidity", "soil"),
+            Err(Error::NoPath { .. })
+        ));
+        assert!(matches!(
+            input.path("location", "seed"),
+            Err(Error::UnknownCategory { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn stages_may_be_reordered_or_extended() -> Result<(), Error> {
+        let mut sections = EXAMPLE_INPUT_1.trim_end().split("\n\n").collect::<Vec<_>>();
+        sections[1..].reverse();
+        sections.push("location-to-planet map:\n0 82 1");
+        let almanac = sections.join("\n\n");
+
+        let input = parse_input(&almanac)?;
+        assert_eq!(35, lowest_seed_location(&input)?);
+        assert_eq!(46, lowest_seed_range_location(&input)?);
+        assert_eq!(0, input.path(SEED, "planet")?.get(79));
+
+        Ok(())
+    }
+
+    #[test]
+    fn stages_must_chain() {
+        let broken = "seeds: 1\n\na-to-b map:\n0 0 1\n\nc-to-d map:\n0 0 1\n";
+        assert!(matches!(
+            parse_almanac(broken),
+            Err(ParseInputError::Disconnected { .. })
+        ));
+
+        let duplicate = "seeds: 1\n\na-to-b map:\n0 0 1\n\na-to-c map:\n0 0 1\n";
+        assert!(matches!(
+            parse_almanac(duplicate),
+            Err(ParseInputError::DuplicateStage { .. })
+        ));
+
+        let header = "seeds: 1\n\na to b:\n0 0 1\n";
+        assert!(matches!(
+            parse_almanac(header),
+            Err(ParseInputError::InvalidHeader { .. })
+        ));
+    }
+
+    #[test]
+    #[snafu::report]
+    fn invert_requires_one_to_one() -> Result<(), ParseMapError> {
+        let map = parse_map(["50 98 2", "52 50 48"])?;
+        let inverted = map.invert().expect("Map is one-to-one");
+        for key in 0..120 {
+            assert_eq!(key, inverted.get(map.get(key)));
+        }
+
+        let map = parse_map(["50 98 2"])?;
+        assert!(map.invert().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn ranges_split_at_mapping_edges() -> Result<(), ParseMapError> {
+        let map = parse_map(["50 98 2", "52 50 48"])?;
+
+        assert_eq!(vec![40..50, 52..54], map.get_range(40..52));
+        assert_eq!(vec![97..100, 50..52, 100..105], map.get_range(95..105));
+        assert_eq!(vec![0..10], map.get_range(0..10));
+        assert_eq!(Vec::<ops::Range<u64>>::new(), map.get_range(7..7));
+
+        for seed in 40..110 {
+            let location = map.get(seed);
+            assert_eq!(vec![location..location + 1], map.get_range(seed..seed + 1));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,337 @@
+use aoc_core::Solution;
+use itertools::Either;
+use num_bigint::BigUint;
+use snafu::prelude::*;
+use std::{fmt, iter};
+
+/// Day 6: Wait For It
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input<'a> = Sheet;
+    type Error = Error;
+    type Part1 = u64;
+    type Part2 = BigUint;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        Ok(product_of_number_of_possible_wins(input))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        Ok(number_of_possible_wins_fixed_kerning(input))
+    }
+}
+
+pub fn product_of_number_of_possible_wins(sheet: &Sheet) -> u64 {
+    sheet
+        .races
+        .iter()
+        .map(|race| number_of_possible_wins(race.time, race.distance))
+        .product()
+}
+
+pub fn number_of_possible_wins_fixed_kerning(sheet: &Sheet) -> BigUint {
+    fixed_kerning_hold_times(sheet).map_or_else(BigUint::default, |h| h.len())
+}
+
+pub fn fixed_kerning_hold_times(sheet: &Sheet) -> Option<HoldTimes> {
+    let BigRace { time, distance } = &sheet.fixed_kerning;
+    winning_hold_times(time, distance)
+}
+
+#[derive(Debug, Clone)]
+pub struct Sheet {
+    races: Vec<Race>,
+    /// The single race described when the spaces between digits are ignored
+    fixed_kerning: BigRace,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Race {
+    time: u64,
+    distance: u64,
+}
+
+/// A race whose numbers can have any number of digits.
+#[derive(Debug, Clone)]
+pub struct BigRace {
+    time: BigUint,
+    distance: BigUint,
+}
+
+pub fn parse_input(s: &str) -> Result<Sheet, Error> {
+    let mut lines = s.lines();
+
+    let times = lines.next().context(TimesMissingSnafu)?;
+    let distances = lines.next().context(DistancesMissingSnafu)?;
+
+    let races = parse_sequence(times)
+        .zip(parse_sequence(distances))
+        .map(|(time, distance)| {
+            let time = time.context(TimesInvalidSnafu)?;
+            let distance = distance.context(DistancesInvalidSnafu)?;
+
+            Ok(Race { time, distance })
+        })
+        .collect::<Result<_, _>>()?;
+
+    let smush = |s: &str| {
+        s.chars()
+            .filter(|c| c.is_ascii_digit())
+            .collect::<String>()
+            .parse()
+    };
+
+    let time = smush(times).context(TimeInvalidSnafu)?;
+    let distance = smush(distances).context(DistanceInvalidSnafu)?;
+    let fixed_kerning = BigRace { time, distance };
+
+    Ok(Sheet {
+        races,
+        fixed_kerning,
+    })
+}
+
+// `t` is total time
+// `p` is time spent pressing button
+// `d` is the distance to beat
+//
+// solve for `p`
+// (t-p)*p > d
+// tp - p^2 > d
+// -p^2 + tp - d > 0
+//
+// quadratic formula
+// (-b ± sqrt(b^2 - 4ac)) / 2a
+// a = -1
+// b = t
+// c = -d
+//
+// (-t ± sqrt(t^2 - 4d)) / -2
+// (t ± sqrt(t^2 - 4d)) / 2
+//
+// The integer square root can be off from the real root by less
+// than one, so the estimate for the lower bound is walked to the
+// first winning press. The winners are symmetric around `t/2`, so
+// the upper bound mirrors the lower one.
+fn number_of_possible_wins(time: u64, distance: u64) -> u64 {
+    let t = u128::from(time);
+    let d = u128::from(distance);
+
+    let wins = |p: u128| (t - p) * p > d;
+
+    let Some(discriminant) = (t * t).checked_sub(4 * d) else {
+        return 0;
+    };
+
+    let mut lower = (t - discriminant.isqrt()) / 2;
+    while lower > 0 && wins(lower - 1) {
+        lower -= 1;
+    }
+    while lower <= t / 2 && !wins(lower) {
+        lower += 1;
+    }
+
+    if lower > t / 2 {
+        return 0;
+    }
+    let upper = t - lower;
+
+    // Adding one to account for the fencepost
+    (upper - lower + 1) as u64
+}
+
+/// The shortest and longest presses of the button that win, both
+/// inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HoldTimes {
+    pub lower: BigUint,
+    pub upper: BigUint,
+}
+
+impl HoldTimes {
+    pub fn len(&self) -> BigUint {
+        &self.upper - &self.lower + 1u32
+    }
+}
+
+impl fmt::Display for HoldTimes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]", self.lower, self.upper)
+    }
+}
+
+/// The same approach as [`number_of_possible_wins`], for races too
+/// long to fit in a machine integer.
+pub fn winning_hold_times(time: &BigUint, distance: &BigUint) -> Option<HoldTimes> {
+    let wins = |p: &BigUint| (time - p) * p > *distance;
+
+    let squared = time * time;
+    let quadrupled = distance * 4u32;
+    if squared < quadrupled {
+        return None;
+    }
+    let discriminant = squared - quadrupled;
+
+    let mut lower = (time - discriminant.sqrt()) / 2u32;
+    while lower > BigUint::ZERO && wins(&(&lower - 1u32)) {
+        lower -= 1u32;
+    }
+
+    let half = time / 2u32;
+    while lower <= half && !wins(&lower) {
+        lower += 1u32;
+    }
+
+    if lower > half {
+        return None;
+    }
+    let upper = time - &lower;
+
+    Some(HoldTimes { lower, upper })
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    TimesMissing,
+
+    DistancesMissing,
+
+    TimesInvalid {
+        source: ParseSequenceError,
+    },
+
+    DistancesInvalid {
+        source: ParseSequenceError,
+    },
+
+    TimeInvalid {
+        source: num_bigint::ParseBigIntError,
+    },
+
+    DistanceInvalid {
+        source: num_bigint::ParseBigIntError,
+    },
+}
+
+fn parse_sequence(s: &str) -> impl Iterator<Item = Result<u64, ParseSequenceError>> + '_ {
+    use parse_sequence_error::*;
+
+    match s.split_once(':').context(MalformedSnafu) {
+        Ok((_, values)) => Either::Left(
+            values
+                .split_ascii_whitespace()
+                .map(|value| value.parse().context(InvalidSnafu { value })),
+        ),
+        Err(err) => Either::Right(iter::once(Err(err))),
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum ParseSequenceError {
+    Malformed,
+
+    Invalid {
+        source: std::num::ParseIntError,
+        value: String,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), Error> {
+        let sheet = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(288, product_of_number_of_possible_wins(&sheet));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2() -> Result<(), Error> {
+        let sheet = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(
+            BigUint::from(71503u32),
+            number_of_possible_wins_fixed_kerning(&sheet)
+        );
+
+        let hold_times = fixed_kerning_hold_times(&sheet).unwrap();
+        assert_eq!("[14, 71516]", hold_times.to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn races_without_time() {
+        assert_eq!(0, number_of_possible_wins(0, 0));
+    }
+
+    #[test]
+    fn exact_beyond_float_precision() {
+        // A tie at both ends of the interval, with values past 2^53
+        let (lower, upper) = (2_000_000_000, 4_000_000_001);
+        let time = lower + upper;
+        let distance = lower * upper;
+
+        assert_eq!(upper - lower - 1, number_of_possible_wins(time, distance));
+        assert_eq!(
+            upper - lower + 1,
+            number_of_possible_wins(time, distance - 1)
+        );
+    }
+
+    #[test]
+    fn huge_races() {
+        let ten = BigUint::from(10u32);
+        let lower = ten.pow(200) + 7u32;
+        let upper = ten.pow(200) * 3u32 + 1u32;
+        let time = &lower + &upper;
+        let distance = &lower * &upper;
+
+        let tie = winning_hold_times(&time, &distance).unwrap();
+        assert_eq!(&lower + 1u32, tie.lower);
+        assert_eq!(&upper - 1u32, tie.upper);
+
+        let hold_times = winning_hold_times(&time, &(&distance - 1u32)).unwrap();
+        assert_eq!(HoldTimes { lower, upper }, hold_times);
+
+        let unwinnable = winning_hold_times(&time, &(&time * &time));
+        assert_eq!(None, unwinnable);
+    }
+
+    fn brute_force(time: u64, distance: u64) -> u64 {
+        (0..=time).filter(|&p| (time - p) * p > distance).count() as u64
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn matches_brute_force(time in 0..500u64, distance in 0..70_000u64) {
+            proptest::prop_assert_eq!(brute_force(time, distance), number_of_possible_wins(time, distance));
+        }
+
+        #[test]
+        fn big_matches_machine_integers(time in 0..500u64, distance in 0..70_000u64) {
+            let big = winning_hold_times(&time.into(), &distance.into());
+            let big = big.map_or_else(BigUint::default, |h| h.len());
+            proptest::prop_assert_eq!(BigUint::from(number_of_possible_wins(time, distance)), big);
+        }
+
+        #[test]
+        fn matches_brute_force_near_ties(time in 0..500u64, p in 0..500u64, offset in 0..3u64) {
+            let p = p.min(time);
+            let distance = ((time - p) * p).saturating_sub(offset);
+            proptest::prop_assert_eq!(brute_force(time, distance), number_of_possible_wins(time, distance));
+        }
+    }
+}
@@ -0,0 +1,718 @@
+use aoc_core::Solution;
+use itertools::Itertools;
+use snafu::prelude::*;
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+/// Day 7: Camel Cards
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input<'a> = Game;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        Ok(total_winnings(input, &Rules::standard()))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        Ok(total_winnings(input, &Rules::jokers()))
+    }
+}
+
+pub fn total_winnings(game: &Game, rules: &Rules) -> usize {
+    rules
+        .rank(game)
+        .iter()
+        .map(|ranked| ranked.rank * ranked.bid)
+        .sum()
+}
+
+/// Every hand and its bid.
+#[derive(Debug, Clone)]
+pub struct Game {
+    hands: Vec<(Hand, usize)>,
+}
+
+pub fn parse_input(s: &str) -> Result<Game, Error> {
+    let mut hands = Vec::<(Hand, usize)>::new();
+
+    for line in s.lines() {
+        let (hand, bid) = parse_line(line).context(InvalidLineSnafu { line })?;
+
+        if let Some((first, _)) = hands.first() {
+            let (expected, actual) = (first.size(), hand.size());
+            ensure!(
+                expected == actual,
+                MixedHandSizesSnafu {
+                    line,
+                    expected,
+                    actual
+                }
+            );
+        }
+
+        hands.push((hand, bid));
+    }
+
+    Ok(Game { hands })
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    InvalidLine {
+        source: ParseLineError,
+        line: String,
+    },
+
+    #[snafu(display("The hand in '{line}' has {actual} cards instead of {expected}"))]
+    MixedHandSizes {
+        line: String,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+fn parse_line(l: &str) -> Result<(Hand, usize), ParseLineError> {
+    use parse_line_error::*;
+
+    let (hand, bid) = l.split_once(' ').context(MalformedSnafu)?;
+    let hand = hand.parse().context(InvalidHandSnafu { hand })?;
+    let bid = bid.parse().context(InvalidBidSnafu { bid })?;
+
+    Ok((hand, bid))
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum ParseLineError {
+    Malformed,
+
+    InvalidHand {
+        source: ParseHandError,
+        hand: String,
+    },
+
+    InvalidBid {
+        source: std::num::ParseIntError,
+        bid: String,
+    },
+}
+
+/// How cards compare to each other and which card, if any, can stand
+/// in for any other when deciding the category of a hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    /// Indexed by [`Card`]
+    strengths: [u8; Card::ALL.len()],
+    wild: Option<Card>,
+}
+
+impl Rules {
+    /// Builds rules from every card ordered from weakest to strongest.
+    pub fn new(ranking: [Card; Card::ALL.len()], wild: Option<Card>) -> Result<Self, RulesError> {
+        let mut strengths = [None; Card::ALL.len()];
+
+        for (strength, card) in (0..).zip(ranking) {
+            let slot = &mut strengths[card as usize];
+            ensure!(slot.is_none(), RulesSnafu { card });
+            *slot = Some(strength);
+        }
+
+        let strengths = strengths.map(|s| s.expect("Every card was ranked exactly once"));
+
+        Ok(Self { strengths, wild })
+    }
+
+    /// Jacks rank between tens and queens and nothing is wild.
+    pub fn standard() -> Self {
+        Self::new(Card::ALL, None).expect("The standard ranking is valid")
+    }
+
+    /// Jacks become jokers: the weakest card on its own, but wild
+    /// when deciding the category.
+    pub fn jokers() -> Self {
+        Self::wild(Card::Jack)
+    }
+
+    /// The given card is the weakest and wild; the rest keep their
+    /// standard order.
+    pub fn wild(card: Card) -> Self {
+        let mut ranking = Card::ALL;
+        let idx = card as usize;
+        ranking[..=idx].rotate_right(1);
+
+        Self::new(ranking, Some(card)).expect("Moving one card keeps every card ranked")
+    }
+
+    pub fn strength(&self, card: Card) -> u8 {
+        self.strengths[card as usize]
+    }
+
+    /// The best category the hand can make, using any wild cards as
+    /// whatever card helps most.
+    pub fn category(&self, hand: &Hand) -> Category {
+        self.classify(hand).category
+    }
+
+    /// The category of the hand along with what the wild cards, if
+    /// any, pretended to be.
+    pub fn classify(&self, hand: &Hand) -> Classification {
+        let mut cards = hand.cards.clone();
+        cards.sort();
+
+        let n_wild = cards.iter().filter(|&&c| Some(c) == self.wild).count();
+
+        let groups = cards
+            .iter()
+            .filter(|&&c| Some(c) != self.wild)
+            .group_by(|&&c| c);
+        let mut groups = groups
+            .into_iter()
+            .map(|(c, g)| (c, g.count()))
+            .collect::<Vec<_>>();
+
+        // Ties go to the stronger card so the substitution is
+        // predictable
+        groups.sort_by_key(|&(c, n)| (n, self.strength(c)));
+
+        if groups.is_empty() {
+            // Every card is wild, so they may as well be aces
+            groups.push((Card::Ace, 0));
+        }
+
+        // Wild cards always do best joining the largest group
+        let substitution = self.wild.filter(|_| n_wild > 0).map(|wild| {
+            let (to, n) = groups.last_mut().expect("A hand has at least one card");
+            *n += n_wild;
+
+            Substitution {
+                wild,
+                count: n_wild,
+                to: *to,
+            }
+        });
+
+        let category = Category::new(groups.iter().map(|&(_, n)| n));
+
+        Classification {
+            category,
+            substitution,
+        }
+    }
+
+    fn key(&self, hand: &Hand) -> (Category, Vec<u8>) {
+        let strengths = hand.cards.iter().map(|&c| self.strength(c)).collect();
+        (self.category(hand), strengths)
+    }
+
+    /// Every hand from weakest to strongest.
+    pub fn rank(&self, game: &Game) -> Vec<RankedHand> {
+        let mut hands = game.hands.clone();
+        hands.sort_by_cached_key(|(hand, _)| self.key(hand));
+
+        hands
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (hand, bid))| RankedHand {
+                rank: idx + 1,
+                classification: self.classify(&hand),
+                hand,
+                bid,
+            })
+            .collect()
+    }
+
+    /// Why one hand beats, loses to, or ties with another.
+    pub fn compare(&self, left: &Hand, right: &Hand) -> Comparison {
+        let l = self.classify(left);
+        let r = self.classify(right);
+
+        let decider = if l.category != r.category {
+            Decider::Category
+        } else {
+            let tie_break = (0..)
+                .zip(left.cards.iter().zip(&right.cards))
+                .find(|(_, (&a, &b))| a != b);
+
+            match tie_break {
+                Some((position, (&l, &r))) => Decider::Card {
+                    position,
+                    left: l,
+                    right: r,
+                },
+                None => Decider::Identical,
+            }
+        };
+
+        Comparison {
+            left: (left.clone(), l),
+            right: (right.clone(), r),
+            ordering: self.key(left).cmp(&self.key(right)),
+            decider,
+        }
+    }
+
+    /// Explains each pair of neighboring hands in the ranking, from
+    /// the weakest pair to the strongest.
+    pub fn explain(&self, game: &Game) -> Vec<Comparison> {
+        self.rank(game)
+            .windows(2)
+            .map(|w| self.compare(&w[0].hand, &w[1].hand))
+            .collect()
+    }
+}
+
+/// A hand's place in the ranking, starting from 1 for the weakest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankedHand {
+    pub rank: usize,
+    pub hand: Hand,
+    pub bid: usize,
+    pub classification: Classification,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Classification {
+    pub category: Category,
+    pub substitution: Option<Substitution>,
+}
+
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.category.fmt(f)?;
+
+        if let Some(Substitution { wild, count, to }) = self.substitution {
+            let (wild, to) = (wild.label(), to.label());
+            let s = if count == 1 { "" } else { "s" };
+            write!(f, ", with {count} {wild}{s} as {to}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The wild cards in a hand standing in for another card.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Substitution {
+    pub wild: Card,
+    pub count: usize,
+    pub to: Card,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    pub left: (Hand, Classification),
+    pub right: (Hand, Classification),
+    pub ordering: Ordering,
+    pub decider: Decider,
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            left: (lh, lc),
+            right: (rh, rc),
+            ordering,
+            decider,
+        } = self;
+
+        let op = match ordering {
+            Ordering::Less => "<",
+            Ordering::Equal => "=",
+            Ordering::Greater => ">",
+        };
+
+        write!(f, "{lh} ({lc}) {op} {rh} ({rc}): ")?;
+
+        match decider {
+            Decider::Category => write!(f, "the categories differ"),
+            Decider::Card {
+                position,
+                left,
+                right,
+            } => write!(
+                f,
+                "card {} is {} vs {}",
+                position + 1,
+                left.label(),
+                right.label()
+            ),
+            Decider::Identical => write!(f, "the cards are identical"),
+        }
+    }
+}
+
+/// What settled a comparison between two hands.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Decider {
+    Category,
+    /// The first position, counting from zero, where the cards differ.
+    Card {
+        position: usize,
+        left: Card,
+        right: Card,
+    },
+    Identical,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("The card {card:?} was ranked more than once"))]
+pub struct RulesError {
+    card: Card,
+}
+
+/// Any number of cards, though every hand in a game has the same
+/// number.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hand {
+    cards: Vec<Card>,
+}
+
+impl Hand {
+    pub fn size(&self) -> usize {
+        self.cards.len()
+    }
+}
+
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.cards.iter().try_for_each(|c| c.label().fmt(f))
+    }
+}
+
+impl FromStr for Hand {
+    type Err = ParseHandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use parse_hand_error::*;
+
+        let cards = s
+            .as_bytes()
+            .iter()
+            .map(|&b| b.try_into().context(InvalidCardSnafu { b }))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        ensure!(!cards.is_empty(), EmptySnafu);
+
+        Ok(Self { cards })
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum ParseHandError {
+    InvalidCard { source: ParseCardError, b: u8 },
+
+    Empty,
+}
+
+/// The shape of a hand: how many cards are in each group of matching
+/// cards, largest group first.
+///
+/// Categories compare group by group, so a bigger largest group wins
+/// and, when that ties, the bigger next group does. For five cards
+/// this is the usual order from high card up to five of a kind.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Category {
+    counts: Vec<usize>,
+}
+
+impl Category {
+    pub fn new(counts: impl IntoIterator<Item = usize>) -> Self {
+        let mut counts = counts.into_iter().filter(|&n| n > 0).collect::<Vec<_>>();
+        counts.sort_by(|a, b| b.cmp(a));
+
+        Self { counts }
+    }
+
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    /// The traditional name of the category for a five-card hand.
+    pub fn name(&self) -> Option<&'static str> {
+        Some(match *self.counts {
+            [5] => "five of a kind",
+            [4, 1] => "four of a kind",
+            [3, 2] => "full house",
+            [3, 1, 1] => "three of a kind",
+            [2, 2, 1] => "two pair",
+            [2, 1, 1, 1] => "one pair",
+            [1, 1, 1, 1, 1] => "high card",
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(name) = self.name() {
+            return name.fmt(f);
+        }
+
+        write!(f, "groups of {}", self.counts.iter().join("+"))
+    }
+}
+
+/// The face of a card, listed in the standard ranking order.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Card {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl Card {
+    pub const ALL: [Self; 13] = {
+        use Card::*;
+        [
+            Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King, Ace,
+        ]
+    };
+
+    pub fn label(self) -> char {
+        b"23456789TJQKA"[self as usize].into()
+    }
+}
+
+impl TryFrom<u8> for Card {
+    type Error = ParseCardError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use Card::*;
+
+        Ok(match value {
+            b'2' => Two,
+            b'3' => Three,
+            b'4' => Four,
+            b'5' => Five,
+            b'6' => Six,
+            b'7' => Seven,
+            b'8' => Eight,
+            b'9' => Nine,
+            b'T' => Ten,
+            b'J' => Jack,
+            b'Q' => Queen,
+            b'K' => King,
+            b'A' => Ace,
+            _ => return ParseCardSnafu.fail(),
+        })
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub struct ParseCardError;
+
+#[cfg(test)]
+mod test {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), Error> {
+        let game = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(6440, total_winnings(&game, &Rules::standard()));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn hand_categories() -> Result<(), ParseHandError> {
+        let rules = Rules::standard();
+        let category = |s: &str| Ok::<_, ParseHandError>(rules.category(&s.parse()?).to_string());
+
+        assert_eq!("five of a kind", category("AAAAA")?);
+        assert_eq!("four of a kind", category("AA8AA")?);
+        assert_eq!("full house", category("23332")?);
+        assert_eq!("three of a kind", category("TTT98")?);
+        assert_eq!("two pair", category("23432")?);
+        assert_eq!("one pair", category("A23A4")?);
+        assert_eq!("high card", category("23456")?);
+        assert_eq!("four of a kind", category("JJJJ2")?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2() -> Result<(), Error> {
+        let game = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(5905, total_winnings(&game, &Rules::jokers()));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn jokers_are_weaker() -> Result<(), ParseHandError> {
+        let rules = Rules::jokers();
+        let l = rules.key(&"JKKK2".parse()?);
+        let r = rules.key(&"QQQQ2".parse()?);
+        assert!(l < r);
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn when_jokers_plurality() -> Result<(), ParseHandError> {
+        let rules = Rules::jokers();
+        let category = |s: &str| Ok::<_, ParseHandError>(rules.category(&s.parse()?).to_string());
+
+        assert_eq!("five of a kind", category("JJJJJ")?);
+        assert_eq!("five of a kind", category("JJJJ2")?);
+        assert_eq!("four of a kind", category("JJJ23")?);
+        assert_eq!("three of a kind", category("JJ234")?);
+        assert_eq!("one pair", category("J2345")?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn deuces_wild() -> Result<(), ParseHandError> {
+        let rules = Rules::wild(Card::Two);
+
+        assert_eq!(Category::new([4, 1]), rules.category(&"2KK2Q".parse()?));
+        assert_eq!(0, rules.strength(Card::Two));
+        assert!(rules.strength(Card::Three) < rules.strength(Card::Jack));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rankings_must_not_repeat() {
+        let mut ranking = Card::ALL;
+        ranking[0] = Card::Ace;
+
+        assert_matches!(
+            Rules::new(ranking, None),
+            Err(RulesError { card: Card::Ace })
+        );
+    }
+
+    #[test]
+    #[snafu::report]
+    fn ranking_explanations() -> Result<(), Error> {
+        let game = parse_input(EXAMPLE_INPUT_1)?;
+        let rules = Rules::standard();
+
+        let ranked = rules.rank(&game);
+        let order = ranked
+            .iter()
+            .map(|r| r.hand.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(["32T3K", "KTJJT", "KK677", "T55J5", "QQQJA"], &*order);
+
+        let explanations = rules
+            .explain(&game)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            [
+                "32T3K (one pair) < KTJJT (two pair): the categories differ",
+                "KTJJT (two pair) < KK677 (two pair): card 2 is T vs K",
+                "KK677 (two pair) < T55J5 (three of a kind): the categories differ",
+                "T55J5 (three of a kind) < QQQJA (three of a kind): card 1 is T vs Q",
+            ],
+            &*explanations,
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn explanations_show_substitutions() -> Result<(), ParseHandError> {
+        let rules = Rules::jokers();
+
+        let comparison = rules.compare(&"2JJJJ".parse()?, &"3333J".parse()?);
+        assert_eq!(
+            "2JJJJ (five of a kind, with 4 Js as 2) < \
+             3333J (five of a kind, with 1 J as 3): card 1 is 2 vs 3",
+            comparison.to_string(),
+        );
+
+        let all_wild = rules.classify(&"JJJJJ".parse()?);
+        assert_eq!(Category::new([5]), all_wild.category);
+
+        Ok(())
+    }
+
+    #[test]
+    fn categories_order_by_group_sizes() {
+        let shapes = [
+            vec![1, 1, 1, 1, 1, 1],
+            vec![2, 1, 1, 1, 1],
+            vec![2, 2, 1, 1],
+            vec![2, 2, 2],
+            vec![3, 1, 1, 1],
+            vec![3, 2, 1],
+            vec![3, 3],
+            vec![4, 1, 1],
+            vec![4, 2],
+            vec![5, 1],
+            vec![6],
+        ];
+        let categories = shapes.map(Category::new);
+
+        assert!(categories.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!("groups of 3+3", categories[6].to_string());
+    }
+
+    #[test]
+    #[snafu::report]
+    fn other_hand_sizes() -> Result<(), Error> {
+        let rules = Rules::jokers();
+
+        let hand = "JJ2233".parse::<Hand>().unwrap();
+        let classification = rules.classify(&hand);
+        assert_eq!([4, 2], classification.category.counts());
+        assert_eq!(
+            Some(Substitution {
+                wild: Card::Jack,
+                count: 2,
+                to: Card::Three
+            }),
+            classification.substitution,
+        );
+
+        let game = parse_input("222 1\nKKQ 10\nJ2Q 100\nQJQ 1000")?;
+        // J2Q makes a pair like KKQ but leads with the weak joker
+        assert_eq!(100 + 2 * 10 + 3 + 4 * 1000, total_winnings(&game, &rules));
+
+        let mixed = parse_input("222 1\n2222 2");
+        assert_matches!(
+            mixed,
+            Err(Error::MixedHandSizes {
+                expected: 3,
+                actual: 4,
+                ..
+            })
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,609 @@
+use aoc_core::Solution;
+use itertools::Itertools;
+use snafu::prelude::*;
+use std::collections::{btree_map::Entry, BTreeMap};
+
+/// Day 8: Haunted Wasteland
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input<'a> = Network<'a>;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        Ok(n_steps_to_end(input))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        multi_n_steps_to_end(input)
+    }
+}
+
+pub fn n_steps_to_end(network: &Network<'_>) -> usize {
+    let Network { steps, map } = network;
+
+    let path = follow_path(steps, "AAA", map);
+
+    path.take_while(|&node| node != "ZZZ").count()
+}
+
+/// The fewest steps until every ghost, starting on each node ending
+/// in `A`, stands on a node ending in `Z` at the same time.
+pub fn multi_n_steps_to_end(network: &Network<'_>) -> Result<usize, Error> {
+    meeting_step(ghosts(network))
+}
+
+/// The cycle of the ghost starting on each node ending in `A`, in the
+/// order of their starting nodes.
+pub fn ghosts(network: &Network<'_>) -> Vec<Ghost> {
+    let Network { steps, map } = network;
+
+    map.keys()
+        .filter(|node| node.ends_with('A'))
+        .map(|start| Ghost::new(steps, start, map))
+        .collect()
+}
+
+/// Where one ghost can be found on a node ending in `Z`.
+///
+/// The path from the start runs for `prefix` steps and then repeats
+/// every `length` steps forever.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ghost {
+    pub prefix: usize,
+    pub length: usize,
+    /// Every step, before the path first returns to a state it has
+    /// been in, that lands on an end. Those at or past `prefix`
+    /// recur every `length` steps.
+    pub ends: Vec<usize>,
+}
+
+impl Ghost {
+    fn new<'a>(steps: &'a [Direction], start: &'a str, map: &'a Map<'a>) -> Self {
+        let mut path = follow_path(steps, start, map);
+
+        let ends = path
+            .by_ref()
+            .enumerate()
+            .filter(|(_, node)| node.ends_with('Z'))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        let prefix = path.loop_start.expect("A path only stops when it loops");
+        let length = path.n_walked - prefix;
+
+        Self {
+            prefix,
+            length,
+            ends,
+        }
+    }
+
+    fn meetings(&self) -> impl Iterator<Item = Meeting> + '_ {
+        self.ends.iter().map(|&end| {
+            if end < self.prefix {
+                Meeting::Once(end as u128)
+            } else {
+                Meeting::Every {
+                    residue: end as u128 % self.length as u128,
+                    modulus: self.length as u128,
+                    from: end as u128,
+                }
+            }
+        })
+    }
+}
+
+/// A set of steps at which ghosts could all be on an end.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Meeting {
+    Once(u128),
+    /// Every step congruent to `residue`, starting at `from`
+    Every {
+        residue: u128,
+        modulus: u128,
+        from: u128,
+    },
+}
+
+impl Meeting {
+    fn contains(self, step: u128) -> bool {
+        match self {
+            Meeting::Once(s) => s == step,
+            Meeting::Every {
+                residue,
+                modulus,
+                from,
+            } => step >= from && step % modulus == residue,
+        }
+    }
+
+    /// The steps in both meetings, if there are any, or an error if
+    /// they are too far apart to count.
+    fn intersect(self, other: Self) -> Result<Option<Self>, Error> {
+        use Meeting::*;
+
+        match (self, other) {
+            (Once(s), o) | (o, Once(s)) => Ok(o.contains(s).then_some(Once(s))),
+
+            (
+                Every {
+                    residue: r1,
+                    modulus: m1,
+                    from: f1,
+                },
+                Every {
+                    residue: r2,
+                    modulus: m2,
+                    from: f2,
+                },
+            ) => {
+                let Some((residue, modulus)) = crt((r1, m1), (r2, m2))? else {
+                    return Ok(None);
+                };
+                let from = f1.max(f2);
+
+                Ok(Some(Every {
+                    residue,
+                    modulus,
+                    from,
+                }))
+            }
+        }
+    }
+
+    fn first(self) -> Option<u128> {
+        match self {
+            Meeting::Once(s) => Some(s),
+            Meeting::Every {
+                residue,
+                modulus,
+                from,
+            } => {
+                // Both are below `modulus`, so this can't overflow even
+                // when `modulus` is near `u128::MAX`
+                let offset = from % modulus;
+                let wait = if residue >= offset {
+                    residue - offset
+                } else {
+                    modulus - (offset - residue)
+                };
+                from.checked_add(wait)
+            }
+        }
+    }
+}
+
+/// Combines `x ≡ r1 (mod m1)` and `x ≡ r2 (mod m2)` into a single
+/// congruence, even when the moduli share factors. Fails when the
+/// combined modulus or any step on the way doesn't fit.
+fn crt((r1, m1): (u128, u128), (r2, m2): (u128, u128)) -> Result<Option<(u128, u128)>, Error> {
+    let signed = |n| i128::try_from(n).ok().context(MeetingTooLateSnafu);
+
+    let (g, p, _) = extended_gcd(signed(m1)?, signed(m2)?);
+    let diff = signed(r2)? - signed(r1)?;
+
+    if diff % g != 0 {
+        return Ok(None);
+    }
+
+    // m1 * k ≡ r2 - r1 (mod m2), solved for k
+    let m2_g = signed(m2)? / g;
+    let k = (diff / g % m2_g)
+        .checked_mul(p)
+        .context(MeetingTooLateSnafu)?
+        .rem_euclid(m2_g);
+
+    let g = g.unsigned_abs();
+    let lcm = (m1 / g).checked_mul(m2).context(MeetingTooLateSnafu)?;
+    let residue = m1
+        .checked_mul(k.unsigned_abs())
+        .and_then(|n| n.checked_add(r1))
+        .context(MeetingTooLateSnafu)?
+        % lcm;
+
+    Ok(Some((residue, lcm)))
+}
+
+/// Returns `(g, x, y)` where `a * x + b * y = g = gcd(a, b)`.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - a / b * y)
+    }
+}
+
+fn meeting_step(ghosts: impl IntoIterator<Item = Ghost>) -> Result<usize, Error> {
+    let mut ghosts = ghosts.into_iter();
+
+    let first = ghosts.next().context(NoGhostsSnafu)?;
+    let mut meetings = first.meetings().collect::<Vec<_>>();
+
+    for ghost in ghosts {
+        meetings = meetings
+            .iter()
+            .cartesian_product(ghost.meetings().collect::<Vec<_>>())
+            .map(|(a, b)| a.intersect(b))
+            .flatten_ok()
+            .collect::<Result<Vec<_>, _>>()?;
+        meetings = meetings.into_iter().sorted().dedup().collect();
+    }
+
+    let step = meetings
+        .into_iter()
+        .map(|meeting| meeting.first().context(MeetingTooLateSnafu))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .min()
+        .context(NoMeetingSnafu)?;
+
+    usize::try_from(step).ok().context(MeetingTooLateSnafu)
+}
+
+/// Walks the map from `position`, stopping once it reaches a node at
+/// the same point in the step list as an earlier visit, since the
+/// path repeats forever from there.
+fn follow_path<'a>(steps: &'a [Direction], position: &'a str, map: &'a Map<'a>) -> Path<'a> {
+    Path {
+        steps,
+        map,
+        position,
+        visited: BTreeMap::new(),
+        n_walked: 0,
+        loop_start: None,
+    }
+}
+
+struct Path<'a> {
+    steps: &'a [Direction],
+    map: &'a Map<'a>,
+    position: &'a str,
+    /// The first step each node was reached at each point in the
+    /// step list.
+    visited: BTreeMap<(&'a str, usize), usize>,
+    n_walked: usize,
+    /// The step that the path returned to, once it has.
+    loop_start: Option<usize>,
+}
+
+impl<'a> Iterator for Path<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.loop_start.is_some() {
+            return None;
+        }
+
+        let step_idx = self.n_walked % self.steps.len();
+
+        match self.visited.entry((self.position, step_idx)) {
+            Entry::Occupied(e) => {
+                // We have already visited this node at this point in the step list;
+                // the path will repeat forever.
+                self.loop_start = Some(*e.get());
+                return None;
+            }
+            Entry::Vacant(e) => e.insert(self.n_walked),
+        };
+
+        let map_value = self
+            .map
+            .get(self.position)
+            .expect("the map does not have this node");
+
+        let next = match self.steps[step_idx] {
+            Direction::Left => map_value.0,
+            Direction::Right => map_value.1,
+        };
+
+        let current = self.position;
+        self.position = next;
+        self.n_walked += 1;
+        Some(current)
+    }
+}
+
+type Map<'a> = BTreeMap<&'a str, (&'a str, &'a str)>;
+
+#[derive(Debug, Clone)]
+pub struct Network<'a> {
+    steps: Vec<Direction>,
+    map: Map<'a>,
+}
+
+pub fn parse_input(s: &str) -> Result<Network<'_>, Error> {
+    let mut lines = s.lines().fuse();
+
+    let steps = lines.next().context(StepsMissingSnafu)?;
+    let steps = steps
+        .chars()
+        .map(|c| Direction::try_from(c).context(StepInvalidSnafu { c }))
+        .collect::<Result<_, _>>()?;
+
+    let map = lines
+        .dropping(1)
+        .map(|line| parse_line(line).context(LineInvalidSnafu { line }))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Network { steps, map })
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    StepsMissing,
+
+    #[snafu(display("There are no nodes ending in 'A' for ghosts to start from"))]
+    NoGhosts,
+
+    #[snafu(display("The ghosts are never all on nodes ending in 'Z' at once"))]
+    NoMeeting,
+
+    #[snafu(display("The ghosts meet later than can be counted"))]
+    MeetingTooLate,
+
+    StepInvalid {
+        source: ParseDirectionError,
+        c: char,
+    },
+
+    LineInvalid {
+        source: ParseLineError,
+        line: String,
+    },
+}
+
+fn parse_line(line: &str) -> Result<(&str, (&str, &str)), ParseLineError> {
+    use parse_line_error::*;
+
+    let (key, value) = line.split_once('=').context(MalformedSnafu)?;
+
+    let key = key.trim();
+    let value = value.trim().trim_start_matches('(').trim_end_matches(')');
+
+    let (left, right) = value
+        .split_once(',')
+        .context(MalformedValueSnafu { value })?;
+
+    let left = left.trim();
+    let right = right.trim();
+
+    Ok((key, (left, right)))
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+pub enum ParseLineError {
+    Malformed,
+
+    MalformedValue { value: String },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Direction {
+    Left,
+    Right,
+}
+
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Ok(match value {
+            'L' => Self::Left,
+            'R' => Self::Right,
+            _ => return ParseDirectionSnafu.fail(),
+        })
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub struct ParseDirectionError;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+    const EXAMPLE_INPUT_2: &str = include_str!("../example-input-2");
+    const EXAMPLE_INPUT_3: &str = include_str!("../example-input-3");
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), Error> {
+        let network = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(2, n_steps_to_end(&network));
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2() -> Result<(), Error> {
+        let network = parse_input(EXAMPLE_INPUT_2)?;
+        assert_eq!(6, n_steps_to_end(&network));
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_3() -> Result<(), Error> {
+        let network = parse_input(EXAMPLE_INPUT_3)?;
+        assert_eq!(6, multi_n_steps_to_end(&network)?);
+        Ok(())
+    }
+
+    /// Moves every ghost one step at a time until they all stand on
+    /// an end.
+    fn brute_force(network: &Network<'_>, limit: usize) -> Option<usize> {
+        let Network { steps, map } = network;
+
+        let mut ghosts = map
+            .keys()
+            .filter(|n| n.ends_with('A'))
+            .copied()
+            .collect::<Vec<_>>();
+
+        for (n, step) in steps.iter().cycle().enumerate().take(limit) {
+            if ghosts.iter().all(|g| g.ends_with('Z')) {
+                return Some(n);
+            }
+
+            for g in &mut ghosts {
+                let (l, r) = map[g];
+                *g = match step {
+                    Direction::Left => l,
+                    Direction::Right => r,
+                };
+            }
+        }
+
+        None
+    }
+
+    #[test]
+    #[snafu::report]
+    fn ghost_cycles() -> Result<(), Error> {
+        let network = parse_input(EXAMPLE_INPUT_3)?;
+
+        assert_eq!(
+            [
+                Ghost {
+                    prefix: 1,
+                    length: 2,
+                    ends: vec![2],
+                },
+                Ghost {
+                    prefix: 1,
+                    length: 6,
+                    ends: vec![3, 6],
+                },
+            ],
+            &*ghosts(&network),
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn several_ends_per_cycle() -> Result<(), Error> {
+        let network = parse_input(
+            "L
+
+P0A = (P1Z, P1Z)
+P1Z = (P2B, P2B)
+P2B = (P3Z, P3Z)
+P3Z = (P4B, P4B)
+P4B = (P0A, P0A)
+Q0A = (Q1B, Q1B)
+Q1B = (Q2Z, Q2Z)
+Q2Z = (Q3B, Q3B)
+Q3B = (Q0A, Q0A)",
+        )?;
+
+        assert_eq!(6, multi_n_steps_to_end(&network)?);
+        assert_eq!(Some(6), brute_force(&network, 100));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn ends_before_the_cycle() -> Result<(), Error> {
+        let network = parse_input(
+            "L
+
+11A = (11Z, 11Z)
+11Z = (11A, 11A)
+33A = (33Z, 33Z)
+33Z = (33B, 33B)
+33B = (33C, 33C)
+33C = (33B, 33B)",
+        )?;
+
+        assert_eq!(1, multi_n_steps_to_end(&network)?);
+        assert_eq!(Some(1), brute_force(&network, 100));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn ghosts_that_never_meet() -> Result<(), Error> {
+        let network = parse_input(
+            "L
+
+11A = (11Z, 11Z)
+11Z = (11A, 11A)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22B, 22B)",
+        )?;
+
+        assert!(matches!(
+            multi_n_steps_to_end(&network),
+            Err(Error::NoMeeting)
+        ));
+        assert_eq!(None, brute_force(&network, 100));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn crt_with_shared_factors() -> Result<(), Error> {
+        assert_eq!(Some((10, 12)), crt((2, 4), (4, 6))?);
+        assert_eq!(None, crt((1, 4), (2, 6))?);
+        assert_eq!(Some((23, 105)), crt((2, 3), (23, 35))?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn first_meeting_near_the_limit() -> Result<(), Error> {
+        let every = |residue, modulus, from| Meeting::Every {
+            residue,
+            modulus,
+            from,
+        };
+
+        // The moduli are coprime and multiply to exactly `u128::MAX`
+        let a = every(0, (1 << 64) + 1, 0);
+        let b = every(u64::MAX as u128 - 1, u64::MAX as u128, 0);
+        let meeting = a.intersect(b)?.expect("Coprime moduli always meet");
+        let Meeting::Every { modulus, .. } = meeting else {
+            panic!("Two cycles meet in a cycle");
+        };
+        assert_eq!(u128::MAX, modulus);
+        assert_eq!(Some((1 << 127) - (1 << 63) - 1), meeting.first());
+
+        assert_eq!(
+            Some(u128::MAX - 1),
+            every(u128::MAX - 1, u128::MAX, 0).first()
+        );
+        assert_eq!(None, every(1, u128::MAX, 2).first());
+
+        Ok(())
+    }
+
+    #[test]
+    fn crt_overflow() {
+        let big = 1 << 80;
+        assert!(matches!(
+            crt((0, big), (1, big + 1)),
+            Err(Error::MeetingTooLate)
+        ));
+        assert!(matches!(
+            crt((0, u128::MAX), (0, 2)),
+            Err(Error::MeetingTooLate)
+        ));
+    }
+}
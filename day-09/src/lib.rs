@@ -0,0 +1,126 @@
+use aoc_core::Solution;
+use itertools::Itertools;
+use snafu::prelude::*;
+
+pub type History = Vec<i64>;
+
+/// Day 9: Mirage Maintenance
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input<'a> = Vec<History>;
+    type Error = Error;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        Ok(sum_of_extrapolated_histories(input))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        Ok(sum_of_backwards_extrapolated_histories(input))
+    }
+}
+
+pub fn sum_of_extrapolated_histories(histories: &[History]) -> i64 {
+    histories.iter().map(|h| extrapolated_history(h)).sum()
+}
+
+pub fn sum_of_backwards_extrapolated_histories(histories: &[History]) -> i64 {
+    histories
+        .iter()
+        .map(|h| backwards_extrapolated_history(h))
+        .sum()
+}
+
+pub fn parse_input(s: &str) -> Result<Vec<History>, Error> {
+    s.lines()
+        .map(|line| {
+            line.split_ascii_whitespace()
+                .map(|number| number.parse().context(InvalidNumberSnafu { number }))
+                .collect()
+        })
+        .collect()
+}
+
+fn extrapolated_history(history: &[i64]) -> i64 {
+    let all_numbers = build_differences(history);
+
+    #[allow(clippy::unnecessary_fold)] // parallel code with backwards impl
+    let next_value = all_numbers
+        .iter()
+        .rev()
+        .flat_map(|ns| ns.last())
+        .fold(0, |acc, v| v + acc);
+
+    next_value
+}
+
+fn backwards_extrapolated_history(history: &[i64]) -> i64 {
+    let all_numbers = build_differences(history);
+
+    let prev_value = all_numbers
+        .iter()
+        .rev()
+        .flat_map(|ns| ns.first())
+        .fold(0, |acc, v| v - acc);
+
+    prev_value
+}
+
+fn build_differences(history: &[i64]) -> Vec<Vec<i64>> {
+    let mut last = history.to_vec();
+    let mut all_numbers = Vec::new();
+
+    loop {
+        let next: Vec<_> = last.iter().tuple_windows().map(|(l, r)| r - l).collect();
+
+        all_numbers.push(last);
+
+        if next.iter().all(|&n| n == 0) {
+            break;
+        }
+
+        last = next;
+    }
+
+    all_numbers
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Could not parse '{number}'"))]
+    InvalidNumber {
+        source: std::num::ParseIntError,
+        number: String,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), Error> {
+        let histories = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(114, sum_of_extrapolated_histories(&histories));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2() -> Result<(), Error> {
+        let histories = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(2, sum_of_backwards_extrapolated_histories(&histories));
+
+        Ok(())
+    }
+}
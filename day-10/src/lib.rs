@@ -0,0 +1,337 @@
+use aoc_core::Solution;
+use grid::{Coord, Direction, Grid};
+use snafu::prelude::*;
+use std::collections::BTreeSet;
+
+pub type Map = Grid<Option<Pipe>>;
+
+/// Day 10: Pipe Maze
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input<'a> = Map;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        furthest_distance_from_start(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        area_inside_loop(input)
+    }
+}
+
+pub fn furthest_distance_from_start(map: &Map) -> Result<usize, Error> {
+    let path = build_path(map)?;
+
+    Ok(path.iter().filter(|(_, p)| p.is_some()).count() / 2)
+}
+
+pub fn area_inside_loop(map: &Map) -> Result<usize, Error> {
+    let path = build_path(map)?;
+
+    #[derive(Debug, Copy, Clone)]
+    enum Space {
+        Inside,
+        Outside,
+    }
+
+    impl Space {
+        fn toggle(self) -> Self {
+            match self {
+                Outside => Inside,
+                Inside => Outside,
+            }
+        }
+    }
+
+    #[derive(Debug, Copy, Clone)]
+    enum State {
+        Known(Space),
+        Wall(
+            /// Which pipe we are looking for to toggle the state
+            Pipe,
+            Space,
+        ),
+    }
+
+    use Pipe::*;
+    use Space::*;
+    use State::*;
+
+    let mut count = 0;
+
+    for row in path.rows() {
+        let mut state = State::Known(Space::Outside);
+
+        for &v in row {
+            match (v, state) {
+                // Vertical wall
+                (Some(NorthSouth), Known(s)) => {
+                    state = Known(s.toggle());
+                }
+
+                // Horizontal wall
+                (Some(EastWest), _) => {
+                    // Do nothing
+                }
+
+                // Entering a wall
+                (Some(SouthEast), Known(s)) => {
+                    state = Wall(NorthWest, s);
+                }
+                (Some(NorthEast), Known(s)) => {
+                    state = Wall(SouthWest, s);
+                }
+
+                // Exiting a wall that keeps going
+                //        |    |
+                // --> ┌--┘ or └--┐
+                //     |          |
+                (Some(SouthWest), Wall(SouthWest, s)) => {
+                    state = Known(s.toggle());
+                }
+                (Some(NorthWest), Wall(NorthWest, s)) => {
+                    state = Known(s.toggle());
+                }
+
+                // Exiting a wall that doubles back
+                //     |  |
+                // --> └--┘ or ┌--┐
+                //             |  |
+                (Some(SouthWest), Wall(NorthWest, s)) => {
+                    state = Known(s);
+                }
+                (Some(NorthWest), Wall(SouthWest, s)) => {
+                    state = Known(s);
+                }
+
+                // Blank space
+                (None, Known(Inside)) => {
+                    count += 1;
+                }
+                (None, Known(Outside)) => {
+                    // Do nothing
+                }
+
+                o => panic!("Bad logic {o:?}"),
+            }
+        }
+    }
+
+    Ok(count)
+}
+
+pub fn parse_input(s: &str) -> Result<Map, Error> {
+    Grid::parse(s, Pipe::try_from_char).context(PipeSnafu)
+}
+
+/// A copy of the map holding only the pipes in the loop.
+fn build_path(map: &Map) -> Result<Map, Error> {
+    let start_c = map
+        .position(|&p| p == Some(Pipe::Start))
+        .context(MissingStartSnafu)?;
+
+    let mut to_visit = BTreeSet::new();
+    let mut visited = Grid::new(map.width(), map.height(), None);
+
+    let start_p = calculate_start_pipe(start_c, map);
+
+    to_visit.insert((start_c, start_p));
+
+    while let Some((start_c, start_p)) = to_visit.pop_first() {
+        for &d in start_p.outgoing() {
+            let Some(c) = map.step(start_c, d) else {
+                continue;
+            };
+
+            if let Some(p) = map[c] {
+                if p.compatible(d) && visited[c].replace(p).is_none() {
+                    to_visit.insert((c, p));
+                }
+            }
+        }
+    }
+
+    // Overwrite the start pipe with the concrete one we determined
+    visited[start_c] = Some(start_p);
+
+    Ok(visited)
+}
+
+fn calculate_start_pipe(coord: Coord, map: &Map) -> Pipe {
+    use Direction::*;
+    use Pipe::*;
+
+    let neighbors = map
+        .neighbors(coord)
+        .filter(|&(c, d)| map[c].is_some_and(|p| p.compatible(d)))
+        .map(|(_, d)| d)
+        .collect::<Vec<_>>();
+
+    match &neighbors[..] {
+        [Up, Down] | [Down, Up] => NorthSouth,
+        [Left, Right] | [Right, Left] => EastWest,
+        [Up, Right] | [Right, Up] => NorthEast,
+        [Up, Left] | [Left, Up] => NorthWest,
+        [Down, Left] | [Left, Down] => SouthWest,
+        [Down, Right] | [Right, Down] => SouthEast,
+        _ => unreachable!(),
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    Pipe {
+        source: grid::ParseError<ParsePipeError>,
+    },
+
+    MissingStart,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Pipe {
+    Start,
+    NorthSouth,
+    EastWest,
+    NorthEast,
+    NorthWest,
+    SouthWest,
+    SouthEast,
+}
+
+impl Pipe {
+    fn try_from_char(value: char) -> Result<Option<Self>, ParsePipeError> {
+        use Pipe::*;
+
+        Ok(Some(match value {
+            'S' => Start,
+            '|' => NorthSouth,
+            '-' => EastWest,
+            'L' => NorthEast,
+            'J' => NorthWest,
+            '7' => SouthWest,
+            'F' => SouthEast,
+            '.' => return Ok(None),
+            _ => return ParsePipeSnafu.fail(),
+        }))
+    }
+
+    fn outgoing(self) -> &'static [Direction] {
+        use Direction::*;
+        use Pipe::*;
+
+        match self {
+            Start => &Direction::ALL,
+            NorthSouth => &[Up, Down],
+            EastWest => &[Left, Right],
+            NorthEast => &[Up, Right],
+            NorthWest => &[Up, Left],
+            SouthWest => &[Down, Left],
+            SouthEast => &[Down, Right],
+        }
+    }
+
+    fn compatible(self, dir: Direction) -> bool {
+        use Direction::*;
+        use Pipe::*;
+
+        // The direction we take coming into the pipe
+        #[allow(clippy::match_like_matches_macro)] // I like the current spacing
+        match (self, dir) {
+            (Start, _) => true,
+
+            (NorthSouth, Up | Down) => true,
+
+            (EastWest, Right | Left) => true,
+
+            (NorthEast, Down | Left) => true,
+
+            (NorthWest, Right | Down) => true,
+
+            (SouthWest, Up | Right) => true,
+
+            (SouthEast, Up | Left) => true,
+
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub struct ParsePipeError;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+    const EXAMPLE_INPUT_1B: &str = include_str!("../example-input-1b");
+    const EXAMPLE_INPUT_2: &str = include_str!("../example-input-2");
+    const EXAMPLE_INPUT_2B: &str = include_str!("../example-input-2b");
+    const EXAMPLE_INPUT_3: &str = include_str!("../example-input-3");
+    const EXAMPLE_INPUT_3B: &str = include_str!("../example-input-3b");
+    const EXAMPLE_INPUT_4: &str = include_str!("../example-input-4");
+    const EXAMPLE_INPUT_5: &str = include_str!("../example-input-5");
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), Error> {
+        assert_eq!(
+            4,
+            furthest_distance_from_start(&parse_input(EXAMPLE_INPUT_1)?)?
+        );
+        assert_eq!(
+            4,
+            furthest_distance_from_start(&parse_input(EXAMPLE_INPUT_1B)?)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2() -> Result<(), Error> {
+        assert_eq!(
+            8,
+            furthest_distance_from_start(&parse_input(EXAMPLE_INPUT_2)?)?
+        );
+        assert_eq!(
+            8,
+            furthest_distance_from_start(&parse_input(EXAMPLE_INPUT_2B)?)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_3() -> Result<(), Error> {
+        assert_eq!(4, area_inside_loop(&parse_input(EXAMPLE_INPUT_3)?)?);
+        assert_eq!(4, area_inside_loop(&parse_input(EXAMPLE_INPUT_3B)?)?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_4() -> Result<(), Error> {
+        assert_eq!(8, area_inside_loop(&parse_input(EXAMPLE_INPUT_4)?)?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_5() -> Result<(), Error> {
+        assert_eq!(10, area_inside_loop(&parse_input(EXAMPLE_INPUT_5)?)?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use aoc_core::Solution;
+use std::{collections::BTreeSet, convert::Infallible};
+
+#[derive(Debug, Clone)]
+pub struct Image {
+    galaxies: Vec<(usize, usize)>,
+    seen_columns: BTreeSet<usize>,
+    seen_rows: BTreeSet<usize>,
+}
+
+/// Day 11: Cosmic Expansion
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Image;
+    type Error = Infallible;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        Ok(sum_of_shortest_paths::<2>(input))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        Ok(sum_of_shortest_paths::<1_000_000>(input))
+    }
+}
+
+pub fn parse_input(s: &str) -> Image {
+    let mut galaxies = Vec::new();
+    let mut seen_columns = BTreeSet::new();
+    let mut seen_rows = BTreeSet::new();
+
+    for (y, line) in s.lines().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            if c == '#' {
+                galaxies.push((x, y));
+
+                seen_columns.insert(x);
+                seen_rows.insert(y);
+            }
+        }
+    }
+
+    Image {
+        galaxies,
+        seen_columns,
+        seen_rows,
+    }
+}
+
+pub fn sum_of_shortest_paths<const RATE: usize>(image: &Image) -> usize {
+    let Image {
+        galaxies,
+        seen_columns,
+        seen_rows,
+    } = image;
+
+    let mut q = &galaxies[..];
+    let mut distance_sum = 0;
+
+    while let Some((head, tails)) = q.split_first() {
+        for tail in tails {
+            let [x0, x1] = order(head.0, tail.0);
+            let [y0, y1] = order(head.1, tail.1);
+
+            let x_base = x1 - x0;
+            let y_base = y1 - y0;
+
+            let x_expansion = (x0..=x1).filter(|x| !seen_columns.contains(x)).count();
+            let y_expansion = (y0..=y1).filter(|y| !seen_rows.contains(y)).count();
+
+            let x = x_base + x_expansion * (RATE - 1);
+            let y = y_base + y_expansion * (RATE - 1);
+
+            distance_sum += x + y;
+        }
+        q = tails;
+    }
+
+    distance_sum
+}
+
+fn order(a: usize, b: usize) -> [usize; 2] {
+    let mut x = [a, b];
+    x.sort();
+    x
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+
+    #[test]
+    fn example_1() {
+        let image = parse_input(EXAMPLE_INPUT_1);
+        assert_eq!(374, sum_of_shortest_paths::<2>(&image));
+    }
+
+    #[test]
+    fn example_2() {
+        let image = parse_input(EXAMPLE_INPUT_1);
+        assert_eq!(1030, sum_of_shortest_paths::<10>(&image));
+        assert_eq!(8410, sum_of_shortest_paths::<100>(&image));
+    }
+}
@@ -0,0 +1,228 @@
+use aoc_core::Solution;
+use grid::Grid;
+use itertools::Itertools;
+use snafu::prelude::*;
+use std::{collections::BTreeMap, convert::Infallible};
+
+/// Day 13: Point of Incidence
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input<'a> = Vec<Pattern<'a>>;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        Ok(summarize(input))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        Ok(summarize_with_smudges(input))
+    }
+}
+
+pub fn summarize(valley: &[Pattern<'_>]) -> usize {
+    valley
+        .iter()
+        .map(|Pattern { raw_grid, grid }| {
+            // Iterate over the grid horizontally and vertically,
+            // reducing each row or column down to a pattern of
+            // booleans, then assigning each unique pattern an ID.
+
+            let (_unique_column_patterns, columns) = build_patterns(grid.columns());
+            let (_unique_row_patterns, rows) = build_patterns(grid.rows());
+
+            // We now have two one-dimensional views of the
+            // grid. Potential fold points occur whenever two pattern
+            // IDs occur next to each other.
+
+            let column_fold_idx = fold_index(&columns);
+            let row_fold_idx = fold_index(&rows);
+
+            to_score(raw_grid, column_fold_idx, row_fold_idx)
+        })
+        .sum()
+}
+
+pub fn summarize_with_smudges(valley: &[Pattern<'_>]) -> usize {
+    valley
+        .iter()
+        .map(|Pattern { raw_grid, grid }| {
+            // Iterate over the grid horizontally and vertically,
+            // reducing each row or column down to a pattern of
+            // booleans, then assigning each unique pattern an ID.
+
+            let (unique_column_patterns, columns) = build_patterns(grid.columns());
+            let (unique_row_patterns, rows) = build_patterns(grid.rows());
+
+            // Find potential swaps by looking at patterns that differ
+            // by only one element.
+
+            let column_swaps = potential_swaps(&unique_column_patterns);
+            let row_swaps = potential_swaps(&unique_row_patterns);
+
+            // Walk through each pattern, replacing each possible
+            // pattern swap at a time, then evaluate if the modified
+            // pattern could be a fold.
+
+            let column_fold_idx = fold_index_with_swaps(&columns, &column_swaps);
+            let row_fold_idx = fold_index_with_swaps(&rows, &row_swaps);
+
+            to_score(raw_grid, column_fold_idx, row_fold_idx)
+        })
+        .sum()
+}
+
+type UniquePatternMap = BTreeMap<Vec<bool>, usize>;
+
+type Patterns = Vec<usize>;
+
+type Swap = [usize; 2];
+
+#[derive(Debug, Clone)]
+pub struct Pattern<'a> {
+    raw_grid: &'a str,
+    grid: Grid<bool>,
+}
+
+pub fn parse_input(s: &str) -> Result<Vec<Pattern<'_>>, Error> {
+    s.split("\n\n")
+        .map(|raw_grid| {
+            let grid = Grid::parse(raw_grid, |c| Ok(c == '#')).context(GridSnafu)?;
+            Ok(Pattern { raw_grid, grid })
+        })
+        .collect()
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    Grid {
+        source: grid::ParseError<Infallible>,
+    },
+}
+
+fn build_patterns<'a>(
+    lines: impl Iterator<Item = impl IntoIterator<Item = &'a bool>>,
+) -> (UniquePatternMap, Patterns) {
+    let mut unique_patterns = BTreeMap::new();
+    let patterns = lines
+        .map(|line| {
+            let pattern = line.into_iter().copied().collect();
+
+            let next_id = unique_patterns.len();
+            *unique_patterns.entry(pattern).or_insert(next_id)
+        })
+        .collect();
+
+    (unique_patterns, patterns)
+}
+
+fn fold_index(patterns: &Patterns) -> Option<usize> {
+    fold_indices(patterns).next()
+}
+
+fn fold_indices(patterns: &Patterns) -> impl Iterator<Item = usize> + '_ {
+    let potential_fold_idxs = patterns
+        .iter()
+        .tuple_windows()
+        .enumerate()
+        .filter(|(_, (a, b))| a == b)
+        .map(|(i, _)| i + 1);
+
+    potential_fold_idxs.filter(|&fold_idx| {
+        let (a, b) = patterns.split_at(fold_idx);
+
+        // Using `zip` instead of `eq` to ignore mismatched lengths
+        a.iter().rev().zip(b).all(|(a, b)| a == b)
+    })
+}
+
+fn to_score(grid: &str, column_fold_idx: Option<usize>, row_fold_idx: Option<usize>) -> usize {
+    match (column_fold_idx, row_fold_idx) {
+        (Some(c), None) => c,
+        (None, Some(r)) => r * 100,
+        (c, r) => {
+            eprintln!("Grid had impossible solution");
+            eprintln!();
+            eprintln!("{grid}");
+            eprintln!();
+            eprintln!("{c:?} / {r:?}");
+            panic!();
+        }
+    }
+}
+
+fn potential_swaps(unique_patterns: &UniquePatternMap) -> Vec<Swap> {
+    unique_patterns
+        .iter()
+        .map(|(k, &v)| (&k[..], v))
+        .combinations(2)
+        .map(|v| <[_; 2]>::try_from(v).expect("Must have two"))
+        .filter(|[a, b]| a.0.iter().zip(b.0).filter(|(a, b)| a != b).count() == 1)
+        .map(|[a, b]| [a.1, b.1])
+        .collect()
+}
+
+fn fold_index_with_swaps(patterns: &Patterns, swaps: &[Swap]) -> Option<usize> {
+    let original_idx = fold_index(patterns);
+
+    patterns
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &pattern)| {
+            let fold_idx_with_swap = move |src, dst| {
+                if pattern == src {
+                    let mut swapped = patterns.clone();
+                    swapped[i] = dst;
+                    fold_indices(&swapped).collect()
+                } else {
+                    vec![]
+                }
+            };
+
+            swaps.iter().flat_map(move |&[a, b]| {
+                let idx_a = fold_idx_with_swap(a, b);
+                let idx_b = fold_idx_with_swap(b, a);
+
+                itertools::chain(idx_a, idx_b)
+            })
+        })
+        .find(|&idx| Some(idx) != original_idx)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+    const REPRO_INPUT_1: &str = include_str!("../repro-input-1");
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), Error> {
+        assert_eq!(405, summarize(&parse_input(EXAMPLE_INPUT_1)?));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2() -> Result<(), Error> {
+        assert_eq!(400, summarize_with_smudges(&parse_input(EXAMPLE_INPUT_1)?));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn repro_1() -> Result<(), Error> {
+        assert_eq!(5, summarize_with_smudges(&parse_input(REPRO_INPUT_1)?));
+
+        Ok(())
+    }
+}
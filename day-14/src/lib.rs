@@ -0,0 +1,288 @@
+use aoc_core::Solution;
+use core::fmt;
+use grid::Grid;
+use snafu::prelude::*;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    convert::Infallible,
+};
+
+pub const CYCLES: usize = 1_000_000_000;
+
+/// Day 14: Parabolic Reflector Dish
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input<'a> = Board;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        Ok(total_load(input))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        Ok(total_load_after_spin_cycles(input, CYCLES))
+    }
+}
+
+pub fn total_load(board: &Board) -> usize {
+    let mut board = board.clone();
+
+    board.tilt_north();
+
+    board.total_load()
+}
+
+pub fn total_load_after_spin_cycles(board: &Board, n_cycles: usize) -> usize {
+    let mut board = board.clone();
+
+    let mut last_states = HashMap::new();
+
+    for cycle in 0..n_cycles {
+        board.spin_cycle();
+
+        match last_states.entry(board.ball_positions()) {
+            Entry::Vacant(v) => v.insert(cycle),
+            Entry::Occupied(o) => {
+                let &prev_cycle = o.get();
+
+                let cycle_len = cycle - prev_cycle;
+
+                let cycles_remaining = n_cycles - cycle;
+                // Skip over all the repeated work
+                let cycles_remaining = cycles_remaining % cycle_len;
+                // We've already done this cycle, don't count it again.
+                let cycles_remaining = cycles_remaining - 1;
+
+                for _ in 0..cycles_remaining {
+                    board.spin_cycle();
+                }
+
+                break;
+            }
+        };
+    }
+
+    board.total_load()
+}
+
+pub fn parse_input(s: &str) -> Result<Board, Error> {
+    let grid = Grid::parse(s, |c| {
+        Ok(match c {
+            'O' => Rock::Ball,
+            '#' => Rock::Cube,
+            _ => Rock::Empty,
+        })
+    })
+    .context(GridSnafu)?;
+
+    Ok(Board { grid })
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    Grid {
+        source: grid::ParseError<Infallible>,
+    },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Rock {
+    Empty,
+    Cube,
+    Ball,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Board {
+    grid: Grid<Rock>,
+}
+
+impl Board {
+    fn total_load(&self) -> usize {
+        let height = self.grid.height();
+
+        // Counting from the *edge* of the platform, so the bottom row
+        // has a load of one.
+        self.grid
+            .iter()
+            .filter(|&(_, &r)| r == Rock::Ball)
+            .map(|((_, y), _)| height - y)
+            .sum()
+    }
+
+    /// A bitset with one bit per cell, set where a ball is. The cubes
+    /// never move, so this is enough to identify the whole board.
+    fn ball_positions(&self) -> Vec<u64> {
+        let mut bits = vec![0; (self.grid.width() * self.grid.height()).div_ceil(64)];
+
+        for (i, rock) in self.grid.cells().iter().enumerate() {
+            if *rock == Rock::Ball {
+                bits[i / 64] |= 1 << (i % 64);
+            }
+        }
+
+        bits
+    }
+
+    fn spin_cycle(&mut self) {
+        self.tilt_north();
+        self.tilt_west();
+        self.tilt_south();
+        self.tilt_east();
+    }
+
+    fn roll(&mut self, from: (usize, usize), to: (usize, usize)) {
+        self.grid[from] = Rock::Empty;
+        self.grid[to] = Rock::Ball;
+    }
+
+    /// Walk down each column. If we see a ball, move it to the
+    /// furthest spot avaiable. If we see a cube, update where the
+    /// furthest spot would be.
+    fn tilt_north(&mut self) {
+        for x in 0..self.grid.width() {
+            let mut dest = 0;
+
+            for y in 0..self.grid.height() {
+                match self.grid[(x, y)] {
+                    Rock::Ball => {
+                        self.roll((x, y), (x, dest));
+                        dest += 1;
+                    }
+                    // A cube is at this spot, so the balls will slide to
+                    // the *next* spot
+                    Rock::Cube => dest = y + 1,
+                    Rock::Empty => {}
+                }
+            }
+        }
+    }
+
+    fn tilt_west(&mut self) {
+        for y in 0..self.grid.height() {
+            let mut dest = 0;
+
+            for x in 0..self.grid.width() {
+                match self.grid[(x, y)] {
+                    Rock::Ball => {
+                        self.roll((x, y), (dest, y));
+                        dest += 1;
+                    }
+                    Rock::Cube => dest = x + 1,
+                    Rock::Empty => {}
+                }
+            }
+        }
+    }
+
+    fn tilt_south(&mut self) {
+        let height = self.grid.height();
+
+        for x in 0..self.grid.width() {
+            let mut dest = height.saturating_sub(1);
+
+            for y in (0..height).rev() {
+                match self.grid[(x, y)] {
+                    Rock::Ball => {
+                        self.roll((x, y), (x, dest));
+                        dest = dest.saturating_sub(1);
+                    }
+                    Rock::Cube => dest = y.saturating_sub(1), // can be zero as we will exit the loop anyway
+                    Rock::Empty => {}
+                }
+            }
+        }
+    }
+
+    fn tilt_east(&mut self) {
+        let width = self.grid.width();
+
+        for y in 0..self.grid.height() {
+            let mut dest = width.saturating_sub(1);
+
+            for x in (0..width).rev() {
+                match self.grid[(x, y)] {
+                    Rock::Ball => {
+                        self.roll((x, y), (dest, y));
+                        dest = dest.saturating_sub(1);
+                    }
+                    Rock::Cube => dest = x.saturating_sub(1), // can be zero as we will exit the loop anyway
+                    Rock::Empty => {}
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.grid.rows() {
+            for rock in row {
+                let c = match rock {
+                    Rock::Cube => '#',
+                    Rock::Ball => 'O',
+                    Rock::Empty => '.',
+                };
+
+                c.fmt(f)?;
+            }
+            "\n".fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+    const EXAMPLE_INPUT_1_CYCLE_1: &str = include_str!("../example-input-1-cycle-1");
+    const EXAMPLE_INPUT_1_CYCLE_2: &str = include_str!("../example-input-1-cycle-2");
+    const EXAMPLE_INPUT_1_CYCLE_3: &str = include_str!("../example-input-1-cycle-3");
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), Error> {
+        let board = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(136, total_load(&board));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2() -> Result<(), Error> {
+        let board = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(64, total_load_after_spin_cycles(&board, CYCLES));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2_spin_cycles() -> Result<(), Error> {
+        let mut b = parse_input(EXAMPLE_INPUT_1)?;
+
+        b.spin_cycle();
+        let b1 = parse_input(EXAMPLE_INPUT_1_CYCLE_1)?;
+        assert_eq!(b1, b);
+
+        b.spin_cycle();
+        let b2 = parse_input(EXAMPLE_INPUT_1_CYCLE_2)?;
+        assert_eq!(b2, b);
+
+        b.spin_cycle();
+        let b3 = parse_input(EXAMPLE_INPUT_1_CYCLE_3)?;
+        assert_eq!(b3, b);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use aoc_core::Solution;
+use snafu::prelude::*;
+
+/// Day 15: Lens Library
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input<'a> = Vec<Step<'a>>;
+    type Error = Error;
+    type Part1 = u64;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        Ok(sum_of_hashes(input))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        Ok(sum_of_focal_power(input))
+    }
+}
+
+pub fn sum_of_hashes(steps: &[Step<'_>]) -> u64 {
+    steps.iter().map(|step| hash(step.raw)).map(u64::from).sum()
+}
+
+pub fn sum_of_focal_power(steps: &[Step<'_>]) -> usize {
+    let mut boxes = vec![Vec::new(); 256];
+
+    for step in steps {
+        match step.operation {
+            Operation::Insert {
+                label,
+                focal_length,
+            } => {
+                let hash = usize::from(hash(label));
+                let the_box = &mut boxes[hash];
+
+                match the_box.iter_mut().find(|(l, _)| *l == label) {
+                    Some(slot) => slot.1 = focal_length,
+                    None => the_box.push((label, focal_length)),
+                }
+            }
+            Operation::Remove { label } => {
+                let hash = usize::from(hash(label));
+                let the_box = &mut boxes[hash];
+
+                the_box.retain(|(l, _)| *l != label);
+            }
+        }
+    }
+
+    // for (i, b) in boxes.iter().enumerate() {
+    //     if b.is_empty() {
+    //         continue;
+    //     }
+    //     eprintln!("{i:3}: {b:?}");
+    // }
+
+    boxes
+        .into_iter()
+        .enumerate()
+        .map(|(box_idx, the_box)| {
+            the_box
+                .into_iter()
+                .enumerate()
+                .map(|(slot_idx, (_, focal_length))| (box_idx + 1) * (slot_idx + 1) * focal_length)
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+/// One comma-separated step of the initialization sequence.
+#[derive(Debug, Copy, Clone)]
+pub struct Step<'a> {
+    raw: &'a str,
+    operation: Operation<'a>,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Operation<'a> {
+    Insert { label: &'a str, focal_length: usize },
+    Remove { label: &'a str },
+}
+
+pub fn parse_input(s: &str) -> Result<Vec<Step<'_>>, Error> {
+    s.split(',')
+        .map(str::trim)
+        .map(|raw| {
+            let operation = if let Some((label, focal_length)) = raw.split_once('=') {
+                let focal_length = focal_length
+                    .parse()
+                    .context(FocalLengthSnafu { focal_length })?;
+
+                Operation::Insert {
+                    label,
+                    focal_length,
+                }
+            } else if let Some(label) = raw.strip_suffix('-') {
+                Operation::Remove { label }
+            } else {
+                return UnknownSnafu { instruction: raw }.fail();
+            };
+
+            Ok(Step { raw, operation })
+        })
+        .collect()
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    FocalLength {
+        source: std::num::ParseIntError,
+        focal_length: String,
+    },
+
+    Unknown {
+        instruction: String,
+    },
+}
+
+fn hash(s: &str) -> u16 {
+    s.as_bytes()
+        .iter()
+        .fold(0, |hash, &byte| ((hash + u16::from(byte)) * 17) % 256)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+
+    #[test]
+    fn example_hash() {
+        assert_eq!(52, hash("HASH"));
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), Error> {
+        let steps = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(1320, sum_of_hashes(&steps));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2() -> Result<(), Error> {
+        let steps = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(145, sum_of_focal_power(&steps));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,200 @@
+use aoc_core::Solution;
+use grid::{Coord, Direction, Grid};
+use snafu::prelude::*;
+use std::str::FromStr;
+
+/// Day 16: The Floor Will Be Lava
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input<'a> = Map;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        Ok(energized_tiles(input))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        maximum_energized_tiles(input)
+    }
+}
+
+pub fn energized_tiles(map: &Map) -> usize {
+    map.energized_tiles_from_start((0, 0), Direction::Right)
+}
+
+pub fn maximum_energized_tiles(map: &Map) -> Result<usize, Error> {
+    use direction_shorthands::*;
+
+    let (x_max, y_max) = map.squares.last_coord().context(EmptySnafu)?;
+
+    let edge_t = (0..=x_max).map(|x| ((x, 0), D));
+    let edge_r = (0..=y_max).map(|y| ((x_max, y), L));
+    let edge_b = (0..=x_max).map(|x| ((x, y_max), U));
+    let edge_l = (0..=y_max).map(|y| ((0, y), R));
+
+    let starts = edge_t.chain(edge_r).chain(edge_b).chain(edge_l);
+
+    starts
+        .map(|(c, d)| map.energized_tiles_from_start(c, d))
+        .max()
+        .context(EmptySnafu)
+}
+
+pub fn parse_input(s: &str) -> Result<Map, Error> {
+    Ok(s.parse()?)
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(context(false))]
+    Parse {
+        source: ParseMapError,
+    },
+
+    Empty,
+}
+
+#[derive(Debug, Clone)]
+pub struct Map {
+    squares: Grid<Option<Square>>,
+}
+
+impl Map {
+    fn energized_tiles_from_start(&self, start: Coord, direction: Direction) -> usize {
+        use direction_shorthands::*;
+        use square_shorthands::*;
+
+        let mut queue = vec![(start, direction)];
+        // One bit for each direction a beam has entered the tile from
+        let mut visited = Grid::new(self.squares.width(), self.squares.height(), 0u8);
+
+        while let Some((coord, direction)) = queue.pop() {
+            let seen = &mut visited[coord];
+            let bit = 1 << direction as u8;
+            if *seen & bit != 0 {
+                // Already visited this, no need to re-visit
+                continue;
+            }
+            *seen |= bit;
+
+            match (self.squares[coord], direction) {
+                (Some(Vs), R | L) => {
+                    for d in [U, D] {
+                        queue.extend(self.cast(coord, d));
+                    }
+                }
+
+                (Some(Hs), U | D) => {
+                    for d in [L, R] {
+                        queue.extend(self.cast(coord, d));
+                    }
+                }
+
+                (Some(Dr), U) => queue.extend(self.cast(coord, L)),
+                (Some(Dr), R) => queue.extend(self.cast(coord, D)),
+                (Some(Dr), D) => queue.extend(self.cast(coord, R)),
+                (Some(Dr), L) => queue.extend(self.cast(coord, U)),
+
+                (Some(Dl), U) => queue.extend(self.cast(coord, R)),
+                (Some(Dl), R) => queue.extend(self.cast(coord, U)),
+                (Some(Dl), D) => queue.extend(self.cast(coord, L)),
+                (Some(Dl), L) => queue.extend(self.cast(coord, D)),
+
+                (Some(Vs), U | D) | (Some(Hs), L | R) | (None, _) => {
+                    queue.extend(self.cast(coord, direction));
+                }
+            }
+        }
+
+        visited.iter().filter(|&(_, &seen)| seen != 0).count()
+    }
+
+    fn cast(&self, start: Coord, dir: Direction) -> Option<(Coord, Direction)> {
+        self.squares.step(start, dir).map(|c| (c, dir))
+    }
+}
+
+impl FromStr for Map {
+    type Err = ParseMapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let squares = Grid::parse(s, Square::try_from).context(SquareSnafu)?;
+
+        Ok(Self { squares })
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum ParseMapError {
+    Square {
+        source: grid::ParseError<ParseSquareError>,
+    },
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Square {
+    VerticalSplit,   // |
+    HorizontalSplit, // -
+    MirrorDownRight, // \
+    MirrorDownLeft,  // /
+}
+
+impl Square {
+    fn try_from(value: char) -> Result<Option<Self>, ParseSquareError> {
+        use Square::*;
+
+        Ok(Some(match value {
+            '|' => VerticalSplit,
+            '-' => HorizontalSplit,
+            '\\' => MirrorDownRight,
+            '/' => MirrorDownLeft,
+            '.' => return Ok(None),
+            _ => return Err(ParseSquareError),
+        }))
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub struct ParseSquareError;
+
+mod square_shorthands {
+    pub(super) use super::Square::{
+        HorizontalSplit as Hs, MirrorDownLeft as Dl, MirrorDownRight as Dr, VerticalSplit as Vs,
+    };
+}
+
+mod direction_shorthands {
+    pub(super) use grid::Direction::{Down as D, Left as L, Right as R, Up as U};
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), Error> {
+        let map = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(46, energized_tiles(&map));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2() -> Result<(), Error> {
+        let map = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(51, maximum_energized_tiles(&map)?);
+
+        Ok(())
+    }
+}
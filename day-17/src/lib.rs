@@ -0,0 +1,181 @@
+use aoc_core::Solution;
+use grid::{Coord, Direction};
+use snafu::prelude::*;
+use std::{cmp, collections::BinaryHeap};
+
+/// The heat lost by entering each city block.
+pub type Grid = grid::Grid<u32>;
+
+/// Day 17: Clumsy Crucible
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input<'a> = Grid;
+    type Error = Error;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        Ok(minimal_heat_loss(input))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        Ok(minimal_heat_loss_ultra(input))
+    }
+}
+
+pub fn minimal_heat_loss(grid: &Grid) -> u32 {
+    minimal_heat_loss_parameterized::<0, 3>(grid)
+}
+
+pub fn minimal_heat_loss_ultra(grid: &Grid) -> u32 {
+    minimal_heat_loss_parameterized::<4, 10>(grid)
+}
+
+pub fn parse_input(s: &str) -> Result<Grid, Error> {
+    let grid = Grid::parse(s, |c| c.to_digit(10).context(ParseDigitSnafu)).context(DigitSnafu)?;
+    ensure!(grid.last_coord().is_some(), EmptySnafu);
+
+    Ok(grid)
+}
+
+fn minimal_heat_loss_parameterized<const MIN: usize, const MAX: usize>(grid: &Grid) -> u32 {
+    /// Arriving at a block at the end of a straight run. Every
+    /// straight run is followed by a turn, so the length of the run
+    /// doesn't need to be tracked.
+    #[derive(Debug)]
+    struct Step {
+        coord: Coord,
+        dir: Direction,
+        cost: u32,
+    }
+
+    impl PartialEq for Step {
+        fn eq(&self, other: &Self) -> bool {
+            self.cost == other.cost
+        }
+    }
+
+    impl Eq for Step {}
+
+    impl PartialOrd for Step {
+        fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Step {
+        fn cmp(&self, other: &Self) -> cmp::Ordering {
+            self.cost.cmp(&other.cost).reverse()
+        }
+    }
+
+    // The crucible has to move at least one block before turning
+    let min = cmp::max(MIN, 1);
+
+    let start = (0, 0);
+    let end = grid.last_coord().expect("Grid has no blocks");
+
+    let mut queue = BinaryHeap::from_iter([
+        Step {
+            coord: start,
+            dir: Direction::Right,
+            cost: 0,
+        },
+        Step {
+            coord: start,
+            dir: Direction::Down,
+            cost: 0,
+        },
+    ]);
+
+    // The cheapest cost seen for every block, split by whether we
+    // arrived moving horizontally or vertically. Laid out flat to
+    // avoid hashing.
+    let mut visited = vec![u32::MAX; grid.width() * grid.height() * 2];
+
+    while let Some(Step { coord, dir, cost }) = queue.pop() {
+        if coord == end {
+            return cost;
+        }
+
+        let block = grid.index_of(coord).expect("Stepped off of the grid");
+        let horizontal = matches!(dir, Direction::Left | Direction::Right);
+        let best_cost = &mut visited[block * 2 + usize::from(horizontal)];
+        if cost < *best_cost {
+            *best_cost = cost;
+        } else {
+            continue;
+        }
+
+        for dir in [dir.turn_left(), dir.turn_right()] {
+            let mut coord = coord;
+            let mut cost = cost;
+
+            for steps in 1..=MAX {
+                let Some(next) = grid.step(coord, dir) else {
+                    break;
+                };
+                coord = next;
+                cost += grid[coord];
+
+                if steps >= min {
+                    queue.push(Step { coord, dir, cost });
+                }
+            }
+        }
+    }
+
+    unreachable!("Grid had no solution");
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    Digit {
+        source: grid::ParseError<ParseDigitError>,
+    },
+
+    Empty,
+}
+
+#[derive(Debug, Snafu)]
+pub struct ParseDigitError;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+    const EXAMPLE_INPUT_2: &str = include_str!("../example-input-2");
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), Error> {
+        let grid = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(102, minimal_heat_loss(&grid));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2() -> Result<(), Error> {
+        let grid = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(94, minimal_heat_loss_ultra(&grid));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_3() -> Result<(), Error> {
+        let grid = parse_input(EXAMPLE_INPUT_2)?;
+        assert_eq!(71, minimal_heat_loss_ultra(&grid));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+use aoc_core::{Solution, Unsolved};
+use grid::{Coord, Grid};
+use snafu::prelude::*;
+use std::{
+    collections::{btree_map::Entry, BTreeMap, VecDeque},
+    convert::Infallible,
+    fmt,
+};
+
+/// Day 21: Step Counter
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input<'a> = Garden;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = Unsolved;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        Ok(unique_reachable_plots(input, 64))
+    }
+
+    fn part2(_: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        Ok(Unsolved)
+    }
+}
+
+pub fn unique_reachable_plots(garden: &Garden, n_steps: usize) -> usize {
+    let Garden { start, ref tiles } = *garden;
+
+    let mut queue = VecDeque::from_iter([(start, n_steps)]);
+    let mut visited = BTreeMap::new();
+
+    // let mut frontier = n_steps;
+
+    while let Some((coord, n_steps_left)) = queue.pop_front() {
+        // if frontier != n_steps_left {
+        //     let gv = GridView {
+        //         tiles,
+        //         visited: &visited,
+        //     };
+        //     eprintln!("-xxx- {frontier}\n\n{gv}");
+        //     frontier = n_steps_left;
+        // }
+
+        match visited.entry(coord) {
+            Entry::Vacant(e) => e.insert(n_steps - n_steps_left),
+            Entry::Occupied(_e) => {
+                // Never toggle between even or odd, right?
+                assert_eq!(
+                    _e.get().is_multiple_of(2),
+                    (n_steps - n_steps_left).is_multiple_of(2)
+                );
+                continue;
+            }
+        };
+
+        if let Some(next_n_steps_left) = n_steps_left.checked_sub(1) {
+            for (next, _) in tiles.neighbors(coord) {
+                if tiles[next] != Tile::Rock {
+                    queue.push_back((next, next_n_steps_left));
+                }
+            }
+        }
+    }
+
+    // let gv = GridView {
+    //     tiles,
+    //     visited: &visited,
+    // };
+    // eprintln!("-xxx- 0\n\n{gv}");
+
+    let is_even = n_steps.is_multiple_of(2);
+    visited.retain(|_, n_steps| n_steps.is_multiple_of(2) == is_even);
+
+    // let gv = GridView {
+    //     tiles,
+    //     visited: &visited,
+    // };
+    // eprintln!("{gv}");
+
+    visited.len()
+}
+
+/// The rocks and the starting position of the garden.
+#[derive(Debug, Clone)]
+pub struct Garden {
+    start: Coord,
+    tiles: Grid<Tile>,
+}
+
+pub fn parse_input(s: &str) -> Result<Garden, Error> {
+    let tiles = Grid::parse(s, |c| {
+        Ok(match c {
+            '#' => Tile::Rock,
+            'S' => Tile::Start,
+            _ => Tile::Plot,
+        })
+    })
+    .context(GridSnafu)?;
+
+    let start = tiles
+        .position(|&t| t == Tile::Start)
+        .context(NoStartSnafu)?;
+
+    Ok(Garden { start, tiles })
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    Grid {
+        source: grid::ParseError<Infallible>,
+    },
+
+    #[snafu(display("The garden has no starting position"))]
+    NoStart,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Tile {
+    Plot,
+    Rock,
+    Start,
+}
+
+#[allow(dead_code)] // Debugging helper
+struct GridView<'a> {
+    tiles: &'a Grid<Tile>,
+    visited: &'a BTreeMap<Coord, usize>,
+}
+
+impl fmt::Display for GridView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.tiles.height() {
+            for x in 0..self.tiles.width() {
+                let coord = (x, y);
+                let rock = self.tiles[coord] == Tile::Rock;
+                let visit = self.visited.get(&coord);
+
+                match (rock, visit) {
+                    (true, Some(_)) => '?'.fmt(f)?,
+                    (true, None) => '#'.fmt(f)?,
+                    (false, Some(s)) => {
+                        if *s < 10 {
+                            s.fmt(f)?;
+                        } else {
+                            'O'.fmt(f)?;
+                        }
+                    }
+                    (false, None) => '.'.fmt(f)?,
+                }
+            }
+            "\n".fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), Error> {
+        let garden = parse_input(EXAMPLE_INPUT_1)?;
+        let cases = [(1, 2), (2, 4), (3, 6), (4, 9), (5, 13), (6, 16)];
+
+        for (steps, plots) in cases {
+            assert_eq!(
+                plots,
+                unique_reachable_plots(&garden, steps),
+                "In {steps} steps"
+            );
+        }
+
+        Ok(())
+    }
+}
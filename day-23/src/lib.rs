@@ -0,0 +1,274 @@
+use aoc_core::Solution;
+use grid::{Coord, Direction, Grid};
+use petgraph::graphmap::UnGraphMap;
+use snafu::prelude::*;
+use std::collections::BTreeSet;
+
+/// Day 23: A Long Walk
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input<'a> = Trails;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1, Self::Error> {
+        Ok(longest_hike(input))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2, Self::Error> {
+        Ok(longest_hike_even_uphill(input))
+    }
+}
+
+pub fn longest_hike(trails: &Trails) -> usize {
+    use Tile::*;
+
+    let Trails {
+        ref map,
+        start,
+        end,
+    } = *trails;
+
+    let mut queue = vec![(start, BTreeSet::new())];
+    let mut successes = vec![];
+
+    while let Some((coord, mut visited)) = queue.pop() {
+        // Reached the end
+        if coord == end {
+            successes.push(visited);
+            continue;
+        }
+
+        // Already been here
+        if !visited.insert(coord) {
+            continue;
+        }
+
+        let directions = match map[coord] {
+            Path => &Direction::ALL[..],
+            Forest => continue,
+            Slope(dir) => &[dir],
+        };
+
+        let nexts = directions.iter().flat_map(|&dir| map.step(coord, dir));
+
+        for next in nexts {
+            queue.push((next, visited.clone()));
+        }
+    }
+
+    let max_len = successes
+        .iter()
+        .map(|s| s.len())
+        .max()
+        .expect("No paths found");
+
+    max_len
+}
+
+pub fn longest_hike_even_uphill(trails: &Trails) -> usize {
+    let Trails {
+        ref map,
+        start,
+        end,
+    } = *trails;
+
+    let mut g = UnGraphMap::new();
+
+    for (coord, tile) in map.iter() {
+        if !tile.passable() {
+            continue;
+        }
+
+        let me = g.add_node(coord);
+
+        for (neighbor, _) in map.neighbors(coord) {
+            if map[neighbor].passable() {
+                let n = g.add_node(neighbor);
+                g.add_edge(me, n, 1);
+            }
+        }
+    }
+
+    // let dot = format!("{:?}", petgraph::dot::Dot::new(&g));
+    // std::fs::write("./graph.dot", dot).unwrap();
+
+    // Find every node that has two edges. Delete that node, summing
+    // up the weights (steps) from each of the two edges and
+    // connecting the outsides.
+    //
+    // A --1-- B --3-- C
+    //
+    // becomes
+    //
+    // A --4-- C
+    //
+    // This is probably the least efficient way of doing this
+    loop {
+        let q = g
+            .nodes()
+            .flat_map(|n| {
+                let e = g.edges(n).collect::<Vec<_>>();
+                <[_; 2]>::try_from(e).map(|es| (n, es))
+            })
+            .next();
+
+        let Some((n, [a, b])) = q else { break };
+
+        let (aa, ab, &aw) = a;
+        let (ba, bb, &bw) = b;
+
+        // What order are these nodes in?
+        let a = if aa == n { ab } else { aa };
+        let b = if ba == n { bb } else { ba };
+
+        g.remove_node(n);
+        g.add_edge(a, b, aw + bw);
+    }
+
+    // let dot = format!("{:?}", petgraph::dot::Dot::new(&g2));
+    // std::fs::write("./graph2.dot", dot).unwrap();
+
+    // Use the same brute force algorithm, but with the compressed graph
+
+    let mut queue = vec![(start, vec![(start, 0)])];
+    let mut successes = vec![];
+
+    while let Some((coord, visited)) = queue.pop() {
+        // Reached the end
+        if coord == end {
+            successes.push(visited);
+            continue;
+        }
+
+        for (a, b, &steps) in g.edges(coord) {
+            let other = if a == coord { b } else { a };
+
+            if visited.iter().any(|&(c, _)| c == other) {
+                // Already been here
+                continue;
+            }
+
+            let mut visited = visited.clone();
+            visited.push((other, steps));
+
+            queue.push((other, visited));
+        }
+    }
+
+    let max_len = successes
+        .iter()
+        .map(|s| s.iter().map(|(_, p)| p).sum::<usize>())
+        .max()
+        .expect("No paths found");
+
+    max_len
+}
+
+type Map = Grid<Tile>;
+
+/// The hiking trail map along with its entry and exit.
+#[derive(Debug, Clone)]
+pub struct Trails {
+    map: Map,
+    start: Coord,
+    end: Coord,
+}
+
+pub fn parse_input(s: &str) -> Result<Trails, Error> {
+    let map = Grid::parse(s, Tile::try_from_char).context(MapSnafu)?;
+    let (start, end) = find_entry_and_exit(&map)?;
+
+    Ok(Trails { map, start, end })
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("The map could not be parsed"))]
+    Map {
+        source: grid::ParseError<ParseTileError>,
+    },
+
+    #[snafu(display("The map has no tiles"))]
+    Empty,
+
+    #[snafu(display("The top row has no path to enter from"))]
+    EntryMissing,
+
+    #[snafu(display("The bottom row has no path to exit from"))]
+    ExitMissing,
+}
+
+fn find_entry_and_exit(map: &Map) -> Result<(Coord, Coord), Error> {
+    let (_, exit_y) = map.last_coord().context(EmptySnafu)?;
+
+    let path_in_row = |y| (0..map.width()).find(|&x| map[(x, y)] == Tile::Path);
+
+    let entry_x = path_in_row(0).context(EntryMissingSnafu)?;
+    let exit_x = path_in_row(exit_y).context(ExitMissingSnafu)?;
+
+    Ok(((entry_x, 0), (exit_x, exit_y)))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Tile {
+    Path,
+    Forest,
+    /// Can only be left in this direction
+    Slope(Direction),
+}
+
+impl Tile {
+    fn try_from_char(c: char) -> Result<Self, ParseTileError> {
+        use Direction::*;
+        use Tile::*;
+
+        Ok(match c {
+            '.' => Path,
+            '#' => Forest,
+            '^' => Slope(Up),
+            '>' => Slope(Right),
+            'v' => Slope(Down),
+            '<' => Slope(Left),
+            _ => return ParseTileSnafu.fail(),
+        })
+    }
+
+    fn passable(self) -> bool {
+        !matches!(self, Tile::Forest)
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub struct ParseTileError;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("../example-input-1");
+
+    #[test]
+    #[snafu::report]
+    fn example_1() -> Result<(), Error> {
+        let trails = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(94, longest_hike(&trails));
+
+        Ok(())
+    }
+
+    #[test]
+    #[snafu::report]
+    fn example_2() -> Result<(), Error> {
+        let trails = parse_input(EXAMPLE_INPUT_1)?;
+        assert_eq!(154, longest_hike_even_uphill(&trails));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,354 @@
+//! A dense two-dimensional grid for the puzzles drawn as a map of
+//! characters.
+
+use snafu::prelude::*;
+use std::{
+    error,
+    ops::{Index, IndexMut},
+};
+
+/// A position in the grid as `(x, y)`, with `(0, 0)` in the top-left
+/// corner.
+pub type Coord = (usize, usize);
+
+/// Every cell of a rectangular map, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Builds a grid from one line of text per row, converting each
+    /// character with `f`. Every row must be the same length.
+    pub fn parse<E>(s: &str, mut f: impl FnMut(char) -> Result<T, E>) -> Result<Self, ParseError<E>>
+    where
+        E: error::Error + 'static,
+    {
+        let mut cells = Vec::with_capacity(s.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for (y, line) in s.lines().enumerate() {
+            let start = cells.len();
+
+            for (x, c) in line.chars().enumerate() {
+                let cell = f(c).context(CellSnafu { x, y, c })?;
+                cells.push(cell);
+            }
+
+            let actual = cells.len() - start;
+            let expected = *width.get_or_insert(actual);
+            ensure!(
+                actual == expected,
+                RaggedSnafu {
+                    y,
+                    expected,
+                    actual
+                }
+            );
+
+            height += 1;
+        }
+
+        let width = width.unwrap_or(0);
+
+        Ok(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The bottom-right corner, if the grid has any cells.
+    pub fn last_coord(&self) -> Option<Coord> {
+        let x = self.width.checked_sub(1)?;
+        let y = self.height.checked_sub(1)?;
+        Some((x, y))
+    }
+
+    pub fn contains(&self, (x, y): Coord) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        let idx = self.index_of(coord)?;
+        self.cells.get(idx)
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        let idx = self.index_of(coord)?;
+        self.cells.get_mut(idx)
+    }
+
+    /// Where `coord` falls when the cells are laid out row by row,
+    /// for callers keeping their own dense per-cell state.
+    pub fn index_of(&self, coord: Coord) -> Option<usize> {
+        let (x, y) = coord;
+        self.contains(coord).then_some(y * self.width + x)
+    }
+
+    /// The neighboring coordinate in `dir`, unless that would leave
+    /// the grid.
+    pub fn step(&self, (x, y): Coord, dir: Direction) -> Option<Coord> {
+        use Direction::*;
+
+        let next = match dir {
+            Up => (x, y.checked_sub(1)?),
+            Right => (x.checked_add(1)?, y),
+            Down => (x, y.checked_add(1)?),
+            Left => (x.checked_sub(1)?, y),
+        };
+
+        self.contains(next).then_some(next)
+    }
+
+    /// The in-bounds orthogonal neighbors along with the direction
+    /// taken to reach each one.
+    pub fn neighbors(&self, coord: Coord) -> impl Iterator<Item = (Coord, Direction)> + '_ {
+        Direction::ALL
+            .into_iter()
+            .flat_map(move |dir| Some((self.step(coord, dir)?, dir)))
+    }
+
+    /// The in-bounds orthogonal and diagonal neighbors.
+    pub fn neighbors_diagonal(&self, (x, y): Coord) -> impl Iterator<Item = Coord> + '_ {
+        const OFFSETS: [(isize, isize); 8] = [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-1, 0),
+            (1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ];
+
+        OFFSETS.into_iter().flat_map(move |(dx, dy)| {
+            let next = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            self.contains(next).then_some(next)
+        })
+    }
+
+    /// Every coordinate, row by row.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell and its coordinate, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(&self.cells)
+    }
+
+    /// The coordinate of the first cell, row by row, matching the
+    /// predicate.
+    pub fn position(&self, mut f: impl FnMut(&T) -> bool) -> Option<Coord> {
+        self.iter().find(|(_, v)| f(v)).map(|(c, _)| c)
+    }
+
+    /// Every cell, row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on zero, but an empty grid has no cells to
+        // split anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.cells[x..].iter().step_by(self.width))
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{coord:?} is outside of the {width}x{height} grid"))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{coord:?} is outside of the {width}x{height} grid"))
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum ParseError<E>
+where
+    E: error::Error + 'static,
+{
+    #[snafu(display("The character '{c}' at ({x}, {y}) is not valid"))]
+    Cell {
+        source: E,
+        x: usize,
+        y: usize,
+        c: char,
+    },
+
+    #[snafu(display("Row {y} has {actual} cells but the rows above have {expected}"))]
+    Ragged {
+        y: usize,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+/// One of the four orthogonal directions, as seen on the screen.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise, starting from `Up`.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn turn_left(self) -> Self {
+        use Direction::*;
+
+        match self {
+            Up => Left,
+            Right => Up,
+            Down => Right,
+            Left => Down,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        use Direction::*;
+
+        match self {
+            Up => Right,
+            Right => Down,
+            Down => Left,
+            Left => Up,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        use Direction::*;
+
+        match self {
+            Up => Down,
+            Right => Left,
+            Down => Up,
+            Left => Right,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{collections::BTreeSet, convert::Infallible};
+
+    fn parse_chars(s: &str) -> Result<Grid<char>, ParseError<Infallible>> {
+        Grid::parse(s, Ok)
+    }
+
+    #[test]
+    #[snafu::report]
+    fn parse_is_row_major() -> Result<(), ParseError<Infallible>> {
+        let grid = parse_chars("ab\ncd\nef\n")?;
+
+        assert_eq!(2, grid.width());
+        assert_eq!(3, grid.height());
+        assert_eq!('d', grid[(1, 1)]);
+        assert_eq!(Some((1, 2)), grid.last_coord());
+        assert_eq!(None, grid.get((2, 0)));
+
+        let rows = grid.rows().map(|r| r.iter().collect::<String>());
+        assert_eq!(vec!["ab", "cd", "ef"], rows.collect::<Vec<_>>());
+
+        let columns = grid.columns().map(|c| c.collect::<String>());
+        assert_eq!(vec!["ace", "bdf"], columns.collect::<Vec<_>>());
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows() {
+        let e = parse_chars("abc\nde\n");
+
+        assert!(
+            matches!(
+                e,
+                Err(ParseError::Ragged {
+                    y: 1,
+                    expected: 3,
+                    actual: 2
+                })
+            ),
+            "{e:?}"
+        );
+    }
+
+    #[test]
+    fn step_stays_in_bounds() {
+        let grid = Grid::new(2, 2, ());
+
+        assert_eq!(None, grid.step((0, 0), Direction::Up));
+        assert_eq!(None, grid.step((0, 0), Direction::Left));
+        assert_eq!(Some((1, 0)), grid.step((0, 0), Direction::Right));
+        assert_eq!(None, grid.step((1, 0), Direction::Right));
+        assert_eq!(None, grid.step((0, 1), Direction::Down));
+    }
+
+    #[test]
+    fn neighbors_handle_edges() {
+        let grid = Grid::new(3, 3, ());
+
+        let found = grid.neighbors((0, 0)).collect::<BTreeSet<_>>();
+        let expected = BTreeSet::from_iter([((1, 0), Direction::Right), ((0, 1), Direction::Down)]);
+        assert_eq!(found, expected);
+
+        let found = grid.neighbors_diagonal((0, 0)).collect::<BTreeSet<_>>();
+        let expected = BTreeSet::from_iter([(1, 0), (1, 1), (0, 1)]);
+        assert_eq!(found, expected);
+
+        let found = grid.neighbors_diagonal((2, 2)).collect::<BTreeSet<_>>();
+        let expected = BTreeSet::from_iter([(1, 1), (2, 1), (1, 2)]);
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn turns_are_inverses() {
+        for d in Direction::ALL {
+            assert_eq!(d, d.turn_left().turn_right());
+            assert_eq!(d.reverse(), d.turn_left().turn_left());
+        }
+    }
+}